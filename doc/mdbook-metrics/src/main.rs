@@ -2,7 +2,15 @@ use mdbook::book::{Book, Chapter};
 use mdbook::errors::Error;
 use mdbook::preprocess::{CmdPreprocessor, Preprocessor, PreprocessorContext};
 use mdbook::BookItem;
-use std::{env, io, path::Path, process::Command};
+use serde_json::Value;
+use std::{collections::HashMap, env, fs, io, path::Path, process::Command};
+
+// Per-challenge coverage written by `autoharness_analyzer challenges` when the Kani metrics are updated
+const CHALLENGE_COVERAGE_PATH: &str = "scripts/kani-std-analysis/challenge_coverage.json";
+const METRICS_DATA_PATHS: [&str; 2] = [
+    "scripts/kani-std-analysis/metrics-data-core.json",
+    "scripts/kani-std-analysis/metrics-data-std.json",
+];
 
 fn main() {
     let mut args = std::env::args().skip(1);
@@ -76,7 +84,37 @@ fn run_kani_metrics_script() -> Result<(), Error> {
     Ok(())
 }
 
-struct AddKaniGraphs;
+// Map each challenge, e.g. "0006-nonnull", to the coverage of the functions it lists.
+// The report is only available once the metrics workflow has run, so a missing file means no coverage.
+fn read_challenge_coverage() -> Result<HashMap<String, Value>, Error> {
+    let Ok(data) = fs::read_to_string(CHALLENGE_COVERAGE_PATH) else {
+        return Ok(HashMap::new());
+    };
+    let challenges: Vec<Value> = serde_json::from_str(&data)?;
+    Ok(challenges
+        .into_iter()
+        .filter_map(|challenge| Some((challenge["id"].as_str()?.to_string(), challenge)))
+        .collect())
+}
+
+// The date of the most recent metrics snapshot, across all crates.
+fn read_metrics_date() -> Result<Option<String>, Error> {
+    let mut latest: Option<String> = None;
+    for path in METRICS_DATA_PATHS {
+        let metrics: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let date = metrics["results"]
+            .as_array()
+            .and_then(|results| results.last())
+            .and_then(|snapshot| snapshot["date"].as_str());
+        latest = latest.max(date.map(String::from));
+    }
+    Ok(latest)
+}
+
+struct AddKaniGraphs {
+    challenge_coverage: HashMap<String, Value>,
+    metrics_date: Option<String>,
+}
 
 impl Preprocessor for AddKaniGraphs {
     fn name(&self) -> &str {
@@ -90,6 +128,16 @@ impl Preprocessor for AddKaniGraphs {
                     add_graphs(ch);
                     return;
                 }
+                let challenge_id = ch
+                    .source_path
+                    .as_ref()
+                    .filter(|path| path.starts_with("challenges"))
+                    .and_then(|path| path.file_stem())
+                    .and_then(|stem| stem.to_str());
+                if let Some(coverage) = challenge_id.and_then(|id| self.challenge_coverage.get(id))
+                {
+                    add_challenge_progress(ch, coverage, self.metrics_date.as_deref());
+                }
             }
         });
         Ok(book)
@@ -120,9 +168,93 @@ Note that these metrics are for x86-64 architectures.
     chapter.content.push_str(new_content);
 }
 
+// Whether the harnesses of a function were written by hand or generated by Kani, given their names.
+fn harness_kind(harnesses: &[Value]) -> &'static str {
+    let automatic = harnesses
+        .iter()
+        .filter(|harness| {
+            harness
+                .as_str()
+                .is_some_and(|name| name.ends_with("automatic_harness"))
+        })
+        .count();
+    match (automatic, harnesses.len() - automatic) {
+        (0, 0) => "",
+        (_, 0) => "Automatic",
+        (0, _) => "Manual",
+        _ => "Manual and automatic",
+    }
+}
+
+// Insert the progress of a challenge after its front matter, i.e., before its first section.
+fn add_challenge_progress(chapter: &mut Chapter, coverage: &Value, metrics_date: Option<&str>) {
+    let Some(functions) = coverage["functions"]
+        .as_array()
+        .filter(|fns| !fns.is_empty())
+    else {
+        return;
+    };
+    let list = |function: &Value, key: &str| function[key].as_array().cloned().unwrap_or_default();
+    let with_contracts = functions
+        .iter()
+        .filter(|f| !list(f, "contracts").is_empty())
+        .count();
+    let with_harnesses = functions
+        .iter()
+        .filter(|f| !list(f, "harnesses").is_empty())
+        .count();
+    let missing = functions
+        .iter()
+        .filter(|f| list(f, "contracts").is_empty() && list(f, "harnesses").is_empty())
+        .count();
+
+    let mut progress = String::from("## Verification Progress\n\n");
+    if let Some(date) = metrics_date {
+        progress.push_str(&format!("*Generated from the Kani metrics of {date}.*\n\n"));
+    }
+    progress.push_str("| Functions | With contracts | With harnesses | Missing |\n");
+    progress.push_str("|-----------|----------------|----------------|---------|\n");
+    progress.push_str(&format!(
+        "| {} | {with_contracts} | {with_harnesses} | {missing} |\n\n",
+        functions.len()
+    ));
+    progress.push_str("| Function | Contract | Harnesses | Harness kind |\n");
+    progress.push_str("|----------|----------|-----------|--------------|\n");
+    for function in functions {
+        let mut name = format!("`{}`", function["name"].as_str().unwrap_or_default());
+        // Some challenges list functions of the same name in different modules
+        if let Some(location) = function["location"].as_str() {
+            name.push_str(&format!(" (`{location}`)"));
+        }
+        let has_contract = if list(function, "contracts").is_empty() {
+            ""
+        } else {
+            "Yes"
+        };
+        let harnesses = list(function, "harnesses");
+        progress.push_str(&format!(
+            "| {name} | {has_contract} | {} | {} |\n",
+            harnesses.len(),
+            harness_kind(&harnesses)
+        ));
+    }
+    progress.push('\n');
+
+    match chapter.content.find("\n## ") {
+        Some(index) => chapter.content.insert_str(index + 1, &progress),
+        None => {
+            chapter.content.push('\n');
+            chapter.content.push_str(&progress);
+        }
+    }
+}
+
 pub fn handle_preprocessing() -> Result<(), Error> {
     run_kani_metrics_script()?;
-    let pre = AddKaniGraphs;
+    let pre = AddKaniGraphs {
+        challenge_coverage: read_challenge_coverage()?,
+        metrics_date: read_metrics_date()?,
+    };
     let (ctx, book) = CmdPreprocessor::parse_input(io::stdin())?;
 
     let processed_book = pre.run(&ctx, book)?;
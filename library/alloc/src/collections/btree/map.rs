@@ -3518,3 +3518,100 @@ impl Error for UnorderedKeyError {}
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+    use core::ub_checks::Invariant;
+
+    use super::*;
+
+    // One more entry than a node can hold, so that trees may get an internal level.
+    const MAX_LEN: usize = node::CAPACITY + 1;
+
+    /// A map of up to `max_len` arbitrary entries.
+    fn any_map(max_len: usize) -> BTreeMap<u8, u8> {
+        let mut map = BTreeMap::new();
+        let len = kani::any_where(|&len: &usize| len <= max_len);
+        for _ in 0..len {
+            map.insert(kani::any(), kani::any());
+        }
+        map
+    }
+
+    /// Checks the invariants of the nodes, and that `length` counts the strictly ascending keys.
+    fn check_invariants(map: &BTreeMap<u8, u8>) {
+        match &map.root {
+            Some(root) => {
+                let root = root.reborrow();
+                assert!(root.ascend().is_err());
+                assert!(root.is_safe());
+            }
+            None => assert_eq!(map.length, 0),
+        }
+        assert_eq!(map.keys().count(), map.length);
+        assert!(map.keys().is_sorted_by(|a, b| a < b));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    pub fn check_insert() {
+        let mut map = any_map(MAX_LEN);
+        let key = kani::any();
+        let value = kani::any();
+        let old_value = map.get(&key).copied();
+        let old_len = map.len();
+
+        assert_eq!(map.insert(key, value), old_value);
+        assert_eq!(map.get(&key), Some(&value));
+        assert_eq!(map.len(), old_len + usize::from(old_value.is_none()));
+        check_invariants(&map);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    pub fn check_remove() {
+        let mut map = any_map(MAX_LEN);
+        let key = kani::any();
+        let old_value = map.get(&key).copied();
+        let old_len = map.len();
+
+        assert_eq!(map.remove(&key), old_value);
+        assert!(!map.contains_key(&key));
+        assert_eq!(map.len(), old_len - usize::from(old_value.is_some()));
+        check_invariants(&map);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    pub fn check_split_off() {
+        let mut map = any_map(MAX_LEN);
+        let key = kani::any();
+        let old_len = map.len();
+
+        let right = map.split_off(&key);
+        assert!(map.keys().all(|k| *k < key));
+        assert!(right.keys().all(|k| *k >= key));
+        assert_eq!(map.len() + right.len(), old_len);
+        check_invariants(&map);
+        check_invariants(&right);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    pub fn check_append() {
+        let mut map = any_map(MAX_LEN);
+        let mut other = any_map(MAX_LEN / 2);
+        let other_entries: Vec<(u8, u8)> = other.iter().map(|(k, v)| (*k, *v)).collect();
+        let max_len = map.len() + other.len();
+
+        map.append(&mut other);
+        assert!(other.is_empty());
+        assert!(map.len() <= max_len);
+        // Entries of `other` replace those of `map` with the same key.
+        assert!(other_entries.iter().all(|(k, v)| map.get(k) == Some(v)));
+        check_invariants(&map);
+        check_invariants(&other);
+    }
+}
@@ -31,10 +31,18 @@
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
 
+#[cfg(kani)]
+use core::kani;
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull};
 use core::slice::SliceIndex;
+// Used only for contract verification.
+#[cfg(any(kani, contracts_runtime_checks))]
+use core::ub_checks;
+use core::ub_checks::Invariant;
+
+use safety::{ensures, invariant, requires};
 
 use crate::alloc::{Allocator, Layout};
 use crate::boxed::Box;
@@ -47,6 +55,13 @@ const EDGE_IDX_LEFT_OF_CENTER: usize = B - 1;
 const EDGE_IDX_RIGHT_OF_CENTER: usize = B;
 
 /// The underlying representation of leaf nodes and part of the representation of internal nodes.
+///
+/// Its invariant bounds `len` and `parent_idx`. That the first `len` keys and values are
+/// initialized cannot be checked, and relies on every write to `len` being preceded by the
+/// corresponding writes to the arrays.
+#[invariant(usize::from(self.len) <= CAPACITY
+    // SAFETY: `parent_idx` is initialized whenever `parent` is non-null.
+    && (self.parent.is_none() || usize::from(unsafe { self.parent_idx.assume_init() }) <= CAPACITY))]
 struct LeafNode<K, V> {
     /// We want to be covariant in `K` and `V`.
     parent: Option<NonNull<InternalNode<K, V>>>,
@@ -67,6 +82,8 @@ struct LeafNode<K, V> {
 
 impl<K, V> LeafNode<K, V> {
     /// Initializes a new `LeafNode` in-place.
+    #[requires(ub_checks::can_write(this))]
+    #[ensures(|_| unsafe { (*this).is_safe() })]
     unsafe fn init(this: *mut Self) {
         // As a general policy, we leave fields uninitialized if they can be, as this should
         // be both slightly faster and easier to track in Valgrind.
@@ -103,6 +120,23 @@ struct InternalNode<K, V> {
     edges: [MaybeUninit<BoxedNode<K, V>>; 2 * B],
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<K, V> Invariant for InternalNode<K, V> {
+    /// The leaf portion is valid, and each of the `len + 1` edges points to a child that links
+    /// back to this node through the index of that edge.
+    fn is_safe(&self) -> bool {
+        self.data.is_safe()
+            && (0..=usize::from(self.data.len)).all(|idx| {
+                // SAFETY: the first `len + 1` edges of an internal node are initialized and
+                // point to valid children.
+                let child = unsafe { self.edges[idx].assume_init_read().as_ref() };
+                child.parent == Some(NonNull::from(self))
+                    // SAFETY: `parent_idx` is initialized whenever `parent` is non-null.
+                    && usize::from(unsafe { child.parent_idx.assume_init() }) == idx
+            })
+    }
+}
+
 impl<K, V> InternalNode<K, V> {
     /// Creates a new boxed `InternalNode`.
     ///
@@ -387,6 +421,24 @@ impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Immut<'a>, K, V, Type> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<'a, K: 'a, V: 'a> Invariant for NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+    /// The node and, if it is internal, every node below it are valid.
+    fn is_safe(&self) -> bool {
+        match self.force() {
+            ForceResult::Leaf(leaf) => leaf.into_leaf().is_safe(),
+            ForceResult::Internal(internal) => {
+                // SAFETY: there can be no mutable references into this tree borrowed as `Immut`.
+                unsafe { &*NodeRef::as_internal_ptr(&internal) }.is_safe()
+                    && (0..=internal.len()).all(|idx| {
+                        // SAFETY: an internal node of length `len` has `len + 1` edges.
+                        unsafe { Handle::new_edge(internal, idx) }.descend().is_safe()
+                    })
+            }
+        }
+    }
+}
+
 impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocates the current node in the process. This is unsafe because the
@@ -789,6 +841,8 @@ impl<Node, Type> Handle<Node, Type> {
 impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::KV> {
     /// Creates a new handle to a key-value pair in `node`.
     /// Unsafe because the caller must ensure that `idx < node.len()`.
+    #[requires(idx < node.len())]
+    #[ensures(|result| result.idx == idx)]
     pub(super) unsafe fn new_kv(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
         debug_assert!(idx < node.len());
 
@@ -865,6 +919,8 @@ impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeTy
 impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
     /// Creates a new handle to an edge in `node`.
     /// Unsafe because the caller must ensure that `idx <= node.len()`.
+    #[requires(idx <= node.len())]
+    #[ensures(|result| result.idx == idx)]
     pub(super) unsafe fn new_edge(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
         debug_assert!(idx <= node.len());
 
@@ -1809,6 +1865,7 @@ pub(super) mod marker {
 ///
 /// # Safety
 /// The slice has more than `idx` elements.
+#[requires(idx < slice.len())]
 unsafe fn slice_insert<T>(slice: &mut [MaybeUninit<T>], idx: usize, val: T) {
     unsafe {
         let len = slice.len();
@@ -1826,6 +1883,7 @@ unsafe fn slice_insert<T>(slice: &mut [MaybeUninit<T>], idx: usize, val: T) {
 ///
 /// # Safety
 /// The slice has more than `idx` elements.
+#[requires(idx < slice.len())]
 unsafe fn slice_remove<T>(slice: &mut [MaybeUninit<T>], idx: usize) -> T {
     unsafe {
         let len = slice.len();
@@ -1841,6 +1899,7 @@ unsafe fn slice_remove<T>(slice: &mut [MaybeUninit<T>], idx: usize) -> T {
 ///
 /// # Safety
 /// The slice has at least `distance` elements.
+#[requires(distance <= slice.len())]
 unsafe fn slice_shl<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
     unsafe {
         let slice_ptr = slice.as_mut_ptr();
@@ -1852,6 +1911,7 @@ unsafe fn slice_shl<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
 ///
 /// # Safety
 /// The slice has at least `distance` elements.
+#[requires(distance <= slice.len())]
 unsafe fn slice_shr<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
     unsafe {
         let slice_ptr = slice.as_mut_ptr();
@@ -1862,6 +1922,7 @@ unsafe fn slice_shr<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
 /// Moves all values from a slice of initialized elements to a slice
 /// of uninitialized elements, leaving behind `src` as all uninitialized.
 /// Works like `dst.copy_from_slice(src)` but does not require `T` to be `Copy`.
+#[requires(src.len() == dst.len())]
 fn move_to_slice<T>(src: &mut [MaybeUninit<T>], dst: &mut [MaybeUninit<T>]) {
     assert!(src.len() == dst.len());
     unsafe {
@@ -1871,3 +1932,110 @@ fn move_to_slice<T>(src: &mut [MaybeUninit<T>], dst: &mut [MaybeUninit<T>]) {
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::alloc::Global;
+
+    /// A leaf root holding up to `CAPACITY` arbitrary key-value pairs.
+    fn any_leaf() -> NodeRef<marker::Owned, u8, u8, marker::Leaf> {
+        let mut leaf = NodeRef::new_leaf(Global);
+        let len = kani::any_where(|&len: &usize| len <= CAPACITY);
+        for _ in 0..len {
+            leaf.borrow_mut().push(kani::any(), kani::any());
+        }
+        leaf
+    }
+
+    /// `CAPACITY` initialized slots, of which the harnesses slice off an arbitrary prefix.
+    fn any_slots() -> [MaybeUninit<u32>; CAPACITY] {
+        kani::any::<[u32; CAPACITY]>().map(MaybeUninit::new)
+    }
+
+    fn any_len() -> usize {
+        kani::any_where(|&len: &usize| len <= CAPACITY)
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    pub fn check_node_invariants() {
+        let mut root = any_leaf().forget_type();
+        assert!(root.reborrow().is_safe());
+        root.push_internal_level(Global);
+        assert!(root.reborrow().is_safe());
+        root.pop_internal_level(Global);
+        assert!(root.reborrow().is_safe());
+        unsafe { root.into_dying().deallocate_and_ascend(Global) };
+    }
+
+    // unsafe fn init(this: *mut Self)
+    #[kani::proof_for_contract(LeafNode::init)]
+    pub fn check_leaf_init() {
+        let mut leaf = MaybeUninit::<LeafNode<u8, u8>>::uninit();
+        unsafe { LeafNode::init(leaf.as_mut_ptr()) };
+    }
+
+    // pub(super) unsafe fn new_kv(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self
+    #[kani::proof_for_contract(Handle::new_kv)]
+    #[kani::unwind(12)]
+    pub fn check_new_kv() {
+        let leaf = any_leaf();
+        let _ = unsafe { Handle::new_kv(leaf.reborrow(), kani::any()) };
+        unsafe { leaf.forget_type().into_dying().deallocate_and_ascend(Global) };
+    }
+
+    // pub(super) unsafe fn new_edge(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self
+    #[kani::proof_for_contract(Handle::new_edge)]
+    #[kani::unwind(12)]
+    pub fn check_new_edge() {
+        let leaf = any_leaf();
+        let _ = unsafe { Handle::new_edge(leaf.reborrow(), kani::any()) };
+        unsafe { leaf.forget_type().into_dying().deallocate_and_ascend(Global) };
+    }
+
+    // unsafe fn slice_insert<T>(slice: &mut [MaybeUninit<T>], idx: usize, val: T)
+    #[kani::proof_for_contract(slice_insert)]
+    pub fn check_slice_insert() {
+        let mut slots = any_slots();
+        let slice = &mut slots[..any_len()];
+        let idx = kani::any();
+        let val = kani::any();
+        unsafe { slice_insert(slice, idx, val) };
+        assert_eq!(unsafe { slice[idx].assume_init() }, val);
+    }
+
+    // unsafe fn slice_remove<T>(slice: &mut [MaybeUninit<T>], idx: usize) -> T
+    #[kani::proof_for_contract(slice_remove)]
+    pub fn check_slice_remove() {
+        let mut slots = any_slots();
+        let original = slots;
+        let slice = &mut slots[..any_len()];
+        let idx = kani::any();
+        let removed = unsafe { slice_remove(slice, idx) };
+        assert_eq!(removed, unsafe { original[idx].assume_init() });
+    }
+
+    // unsafe fn slice_shl<T>(slice: &mut [MaybeUninit<T>], distance: usize)
+    #[kani::proof_for_contract(slice_shl)]
+    pub fn check_slice_shl() {
+        let mut slots = any_slots();
+        unsafe { slice_shl(&mut slots[..any_len()], kani::any()) };
+    }
+
+    // unsafe fn slice_shr<T>(slice: &mut [MaybeUninit<T>], distance: usize)
+    #[kani::proof_for_contract(slice_shr)]
+    pub fn check_slice_shr() {
+        let mut slots = any_slots();
+        unsafe { slice_shr(&mut slots[..any_len()], kani::any()) };
+    }
+
+    // fn move_to_slice<T>(src: &mut [MaybeUninit<T>], dst: &mut [MaybeUninit<T>])
+    #[kani::proof_for_contract(move_to_slice)]
+    pub fn check_move_to_slice() {
+        let mut src = any_slots();
+        let mut dst = [MaybeUninit::uninit(); CAPACITY];
+        move_to_slice(&mut src[..any_len()], &mut dst[..any_len()]);
+    }
+}
@@ -4,11 +4,16 @@
 // Note: This module is also included in the alloctests crate using #[path] to
 // run the tests. See the comment there for an explanation why this is the case.
 
+#[cfg(kani)]
+use core::kani;
 use core::marker::PhantomData;
 use core::mem::{ManuallyDrop, MaybeUninit, SizedTypeProperties};
 use core::ptr::{self, Alignment, NonNull, Unique};
+use core::ub_checks::Invariant;
 use core::{cmp, hint};
 
+use safety::{ensures, invariant, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{Allocator, Global, Layout};
@@ -71,6 +76,7 @@ unsafe fn new_cap<T>(cap: usize) -> Cap {
 /// `usize::MAX`. This means that you need to be careful when round-tripping this type with a
 /// `Box<[T]>`, since `capacity()` won't yield the length.
 #[allow(missing_debug_implementations)]
+#[invariant(self.inner.is_valid_for(T::LAYOUT))]
 pub(crate) struct RawVec<T, A: Allocator = Global> {
     inner: RawVecInner<A>,
     _marker: PhantomData<T>,
@@ -82,7 +88,11 @@ pub(crate) struct RawVec<T, A: Allocator = Global> {
 ///
 /// Having this separation reduces the amount of code we need to monomorphize,
 /// as most operations don't need the actual type, just its layout.
+///
+/// The parts of its invariant that depend on the element layout are checked by
+/// `RawVecInner::is_valid_for`.
 #[allow(missing_debug_implementations)]
+#[invariant(self.cap.as_inner() <= isize::MAX as usize)]
 struct RawVecInner<A: Allocator = Global> {
     ptr: Unique<u8>,
     /// Never used for ZSTs; it's `capacity()`'s responsibility to return usize::MAX in that case.
@@ -450,6 +460,11 @@ impl<A: Allocator> RawVecInner<A> {
         }
     }
 
+    #[requires(elem_layout.size() % elem_layout.align() == 0)]
+    #[ensures(|result| result.is_err() || result.as_ref().unwrap().is_valid_for(elem_layout))]
+    #[ensures(|result| result.is_err()
+        || elem_layout.size() == 0
+        || result.as_ref().unwrap().cap.as_inner() == capacity)]
     fn try_allocate_in(
         capacity: usize,
         init: AllocInit,
@@ -539,6 +554,18 @@ impl<A: Allocator> RawVecInner<A> {
         }
     }
 
+    /// Whether `self` is a valid buffer for elements of `elem_layout`: `ptr` is aligned for
+    /// them, zero-sized elements never get a capacity, and `cap` elements fit in `isize::MAX`
+    /// bytes.
+    fn is_valid_for(&self, elem_layout: Layout) -> bool {
+        let cap = self.cap.as_inner();
+        self.is_safe()
+            && elem_layout.size() % elem_layout.align() == 0
+            && self.ptr.as_ptr().addr() % elem_layout.align() == 0
+            && (elem_layout.size() != 0 || cap == 0)
+            && layout_array(cap, elem_layout).is_ok()
+    }
+
     #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[track_caller]
@@ -636,6 +663,11 @@ impl<A: Allocator> RawVecInner<A> {
         self.cap = unsafe { Cap::new_unchecked(cap) };
     }
 
+    #[requires(additional > 0 && self.is_valid_for(elem_layout))]
+    #[ensures(|_| self.is_valid_for(elem_layout))]
+    #[ensures(|result| result.is_err()
+        || self.capacity(elem_layout.size()) >= len.checked_add(additional).unwrap())]
+    #[ensures(|result| result.is_ok() || self.cap.as_inner() == old(self.cap.as_inner()))]
     fn grow_amortized(
         &mut self,
         len: usize,
@@ -668,6 +700,11 @@ impl<A: Allocator> RawVecInner<A> {
         Ok(())
     }
 
+    #[requires(self.is_valid_for(elem_layout))]
+    #[ensures(|_| self.is_valid_for(elem_layout))]
+    #[ensures(|result| result.is_err()
+        || self.cap.as_inner() == len.checked_add(additional).unwrap())]
+    #[ensures(|result| result.is_ok() || self.cap.as_inner() == old(self.cap.as_inner()))]
     fn grow_exact(
         &mut self,
         len: usize,
@@ -693,6 +730,10 @@ impl<A: Allocator> RawVecInner<A> {
 
     #[cfg(not(no_global_oom_handling))]
     #[inline]
+    #[requires(cap <= self.capacity(elem_layout.size()) && self.is_valid_for(elem_layout))]
+    #[ensures(|_| self.is_valid_for(elem_layout))]
+    #[ensures(|result| result.is_err() || elem_layout.size() == 0 || self.cap.as_inner() == cap)]
+    #[ensures(|result| result.is_ok() || self.cap.as_inner() == old(self.cap.as_inner()))]
     fn shrink(&mut self, cap: usize, elem_layout: Layout) -> Result<(), TryReserveError> {
         assert!(cap <= self.capacity(elem_layout.size()), "Tried to shrink to a larger capacity");
         // SAFETY: Just checked this isn't trying to grow
@@ -763,6 +804,13 @@ impl<A: Allocator> RawVecInner<A> {
 // not marked inline(never) since we want optimizers to be able to observe the specifics of this
 // function, see tests/codegen/vec-reserve-extend.rs.
 #[cold]
+#[requires(current_memory.is_none_or(|(_, old_layout)| {
+    old_layout.align() == new_layout.align() && old_layout.size() <= new_layout.size()
+}))]
+#[ensures(|result| result.is_err()
+    || result.as_ref().unwrap().len() >= new_layout.size())]
+#[ensures(|result| result.is_err()
+    || result.as_ref().unwrap().as_ptr().addr() % new_layout.align() == 0)]
 fn finish_grow<A>(
     new_layout: Layout,
     current_memory: Option<(NonNull<u8>, Layout)>,
@@ -820,3 +868,142 @@ fn alloc_guard(alloc_size: usize) -> Result<(), TryReserveError> {
 fn layout_array(cap: usize, elem_layout: Layout) -> Result<Layout, TryReserveError> {
     elem_layout.repeat(cap).map(|(layout, _pad)| layout).map_err(|_| CapacityOverflow.into())
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    // Capacities are kept small so that the allocations stay tractable.
+    const MAX_CAP: usize = 8;
+
+    /// A buffer of up to `MAX_CAP` elements of `elem_layout`, which may or may not be allocated.
+    fn any_raw_vec_inner(elem_layout: Layout) -> RawVecInner<Global> {
+        let capacity = kani::any_where(|&capacity: &usize| capacity <= MAX_CAP);
+        match RawVecInner::try_allocate_in(capacity, AllocInit::Uninitialized, Global, elem_layout)
+        {
+            Ok(this) => this,
+            Err(_) => RawVecInner::new_in(Global, elem_layout.alignment()),
+        }
+    }
+
+    /// A small number of additional elements, or one that overflows any capacity computation.
+    fn any_additional() -> usize {
+        if kani::any() { kani::any_where(|&n: &usize| n > 0 && n <= MAX_CAP) } else { usize::MAX }
+    }
+
+    macro_rules! generate_raw_vec_harnesses {
+        ($elem:ty, $mod_name:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                const LAYOUT: Layout = <$elem as SizedTypeProperties>::LAYOUT;
+
+                // fn try_allocate_in(
+                //     capacity: usize,
+                //     init: AllocInit,
+                //     alloc: A,
+                //     elem_layout: Layout,
+                // ) -> Result<Self, TryReserveError>
+                #[kani::proof_for_contract(RawVecInner::try_allocate_in)]
+                pub fn check_try_allocate_in() {
+                    let capacity = if kani::any() {
+                        kani::any_where(|&capacity: &usize| capacity <= MAX_CAP)
+                    } else {
+                        usize::MAX
+                    };
+                    let init =
+                        if kani::any() { AllocInit::Uninitialized } else { AllocInit::Zeroed };
+                    if let Ok(mut this) =
+                        RawVecInner::try_allocate_in(capacity, init, Global, LAYOUT)
+                    {
+                        unsafe { this.deallocate(LAYOUT) };
+                    }
+                }
+
+                // fn grow_amortized(
+                //     &mut self,
+                //     len: usize,
+                //     additional: usize,
+                //     elem_layout: Layout,
+                // ) -> Result<(), TryReserveError>
+                #[kani::proof_for_contract(RawVecInner::grow_amortized)]
+                pub fn check_grow_amortized() {
+                    let mut this = any_raw_vec_inner(LAYOUT);
+                    let len = kani::any_where(|&len: &usize| len <= this.capacity(LAYOUT.size()));
+                    let _ = this.grow_amortized(len, any_additional(), LAYOUT);
+                    unsafe { this.deallocate(LAYOUT) };
+                }
+
+                // fn grow_exact(
+                //     &mut self,
+                //     len: usize,
+                //     additional: usize,
+                //     elem_layout: Layout,
+                // ) -> Result<(), TryReserveError>
+                #[kani::proof_for_contract(RawVecInner::grow_exact)]
+                pub fn check_grow_exact() {
+                    let mut this = any_raw_vec_inner(LAYOUT);
+                    let len = kani::any_where(|&len: &usize| len <= this.capacity(LAYOUT.size()));
+                    let _ = this.grow_exact(len, any_additional(), LAYOUT);
+                    unsafe { this.deallocate(LAYOUT) };
+                }
+
+                // fn shrink(&mut self, cap: usize, elem_layout: Layout) -> Result<(), TryReserveError>
+                #[kani::proof_for_contract(RawVecInner::shrink)]
+                pub fn check_shrink() {
+                    let mut this = any_raw_vec_inner(LAYOUT);
+                    let cap = kani::any_where(|&cap: &usize| cap <= this.capacity(LAYOUT.size()));
+                    let _ = this.shrink(cap, LAYOUT);
+                    unsafe { this.deallocate(LAYOUT) };
+                }
+
+                // fn finish_grow<A>(
+                //     new_layout: Layout,
+                //     current_memory: Option<(NonNull<u8>, Layout)>,
+                //     alloc: &mut A,
+                // ) -> Result<NonNull<[u8]>, TryReserveError>
+                #[kani::proof_for_contract(finish_grow)]
+                pub fn check_finish_grow() {
+                    let mut this = any_raw_vec_inner(LAYOUT);
+                    let current_memory = this.current_memory(LAYOUT);
+                    let new_cap = kani::any_where(|&cap: &usize| {
+                        cap >= this.cap.as_inner() && cap <= 2 * MAX_CAP
+                    });
+                    let new_layout = layout_array(new_cap, LAYOUT).unwrap();
+                    match finish_grow(new_layout, current_memory, &mut this.alloc) {
+                        // The old buffer, if any, now belongs to the new one.
+                        Ok(ptr) if new_layout.size() != 0 => unsafe {
+                            Global.deallocate(ptr.cast(), new_layout)
+                        },
+                        Ok(_) => {}
+                        Err(_) => unsafe { this.deallocate(LAYOUT) },
+                    }
+                }
+
+                #[kani::proof]
+                pub fn check_raw_vec_invariant() {
+                    let capacity = kani::any_where(|&capacity: &usize| capacity <= MAX_CAP);
+                    let Ok(mut raw_vec) = RawVec::<$elem>::try_with_capacity_in(capacity, Global)
+                    else {
+                        return;
+                    };
+                    assert!(raw_vec.is_safe());
+                    let len = kani::any_where(|&len: &usize| len <= raw_vec.capacity());
+                    let _ = raw_vec.try_reserve(len, any_additional());
+                    assert!(raw_vec.is_safe());
+                    let cap = kani::any_where(|&cap: &usize| cap <= raw_vec.capacity());
+                    raw_vec.shrink_to_fit(cap);
+                    assert!(raw_vec.is_safe());
+                }
+            }
+        };
+    }
+
+    generate_raw_vec_harnesses!(u8, check_u8);
+    generate_raw_vec_harnesses!(u32, check_u32);
+    generate_raw_vec_harnesses!([u64; 3], check_u64_array);
+    // Zero-sized elements, with the smallest and a larger alignment.
+    generate_raw_vec_harnesses!((), check_unit);
+    generate_raw_vec_harnesses!([u64; 0], check_empty_u64_array);
+}
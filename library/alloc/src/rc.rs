@@ -251,6 +251,8 @@ use core::hash::{Hash, Hasher};
 use core::intrinsics::abort;
 #[cfg(not(no_global_oom_handling))]
 use core::iter;
+#[cfg(kani)]
+use core::kani;
 use core::marker::{PhantomData, Unsize};
 use core::mem::{self, ManuallyDrop, align_of_val_raw};
 use core::num::NonZeroUsize;
@@ -262,8 +264,13 @@ use core::pin::PinCoerceUnsized;
 use core::ptr::{self, NonNull, drop_in_place};
 #[cfg(not(no_global_oom_handling))]
 use core::slice::from_raw_parts_mut;
+// Used only for contract verification.
+#[cfg(any(kani, contracts_runtime_checks))]
+use core::ub_checks::{self, Invariant};
 use core::{borrow, fmt, hint};
 
+use safety::{ensures, invariant, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{AllocError, Allocator, Global, Layout};
@@ -277,7 +284,12 @@ use crate::vec::Vec;
 // This is repr(C) to future-proof against possible field-reordering, which
 // would interfere with otherwise safe [into|from]_raw() of transmutable
 // inner types.
+//
+// The strong pointers collectively hold one weak reference, and the allocation is
+// freed once the weak count drops to zero, so the weak count of a live `RcInner`
+// is never zero, whatever its strong count.
 #[repr(C)]
+#[invariant(self.weak.get() > 0)]
 struct RcInner<T: ?Sized> {
     strong: Cell<usize>,
     weak: Cell<usize>,
@@ -340,11 +352,13 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Rc<U>> for Rc<T> {}
 
 impl<T: ?Sized> Rc<T> {
     #[inline]
+    #[requires(is_live_inner(ptr.as_ptr(), 1))]
     unsafe fn from_inner(ptr: NonNull<RcInner<T>>) -> Self {
         unsafe { Self::from_inner_in(ptr, Global) }
     }
 
     #[inline]
+    #[requires(is_live_inner(ptr, 1))]
     unsafe fn from_ptr(ptr: *mut RcInner<T>) -> Self {
         unsafe { Self::from_inner(NonNull::new_unchecked(ptr)) }
     }
@@ -365,17 +379,20 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     }
 
     #[inline]
+    #[requires(is_live_inner(ptr.as_ptr(), 1))]
     unsafe fn from_inner_in(ptr: NonNull<RcInner<T>>, alloc: A) -> Self {
         Self { ptr, phantom: PhantomData, alloc }
     }
 
     #[inline]
+    #[requires(is_live_inner(ptr, 1))]
     unsafe fn from_ptr_in(ptr: *mut RcInner<T>, alloc: A) -> Self {
         unsafe { Self::from_inner_in(NonNull::new_unchecked(ptr), alloc) }
     }
 
     // Non-inlined part of `drop`.
     #[inline(never)]
+    #[requires(self.inner().strong() == 0 && self.inner().is_safe())]
     unsafe fn drop_slow(&mut self) {
         // Reconstruct the "strong weak" pointer and drop it when this
         // variable goes out of scope. This ensures that the memory is
@@ -1208,6 +1225,7 @@ impl<T, A: Allocator> Rc<mem::MaybeUninit<T>, A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(Rc::as_ptr(&self) as *const T))]
     pub unsafe fn assume_init(self) -> Rc<T, A> {
         let (ptr, alloc) = Rc::into_inner_with_allocator(self);
         unsafe { Rc::from_inner_in(ptr.cast(), alloc) }
@@ -1248,6 +1266,7 @@ impl<T, A: Allocator> Rc<[mem::MaybeUninit<T>], A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(Rc::as_ptr(&self) as *const [T]))]
     pub unsafe fn assume_init(self) -> Rc<[T], A> {
         let (ptr, alloc) = Rc::into_inner_with_allocator(self);
         unsafe { Rc::from_ptr_in(ptr.as_ptr() as _, alloc) }
@@ -1318,6 +1337,7 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -1354,6 +1374,7 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Self::increment_strong_count_in(ptr, Global) }
     }
@@ -1391,6 +1412,7 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Self::decrement_strong_count_in(ptr, Global) }
     }
@@ -1555,6 +1577,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// }
     /// ```
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         let offset = unsafe { data_offset(ptr) };
 
@@ -1659,6 +1682,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1705,6 +1729,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Rc::from_raw_in(ptr, alloc)) };
     }
@@ -1808,6 +1833,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    #[requires(is_live_inner(this.ptr.as_ptr(), 1))]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would conflict with accesses to the reference counts (e.g. by `Weak`).
@@ -2041,6 +2067,7 @@ impl<A: Allocator> Rc<dyn Any, A> {
     /// [`downcast`]: Self::downcast
     #[inline]
     #[unstable(feature = "downcast_unchecked", issue = "90850")]
+    #[requires((*self).type_id() == core::any::TypeId::of::<T>())]
     pub unsafe fn downcast_unchecked<T: Any>(self) -> Rc<T, A> {
         unsafe {
             let (ptr, alloc) = Rc::into_inner_with_allocator(self);
@@ -2056,6 +2083,7 @@ impl<T: ?Sized> Rc<T> {
     /// The function `mem_to_rc_inner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `RcInner<T>`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result))]
     unsafe fn allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -2075,6 +2103,7 @@ impl<T: ?Sized> Rc<T> {
     /// The function `mem_to_rc_inner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `RcInner<T>`.
     #[inline]
+    #[ensures(|result| result.is_err() || is_new_inner(*result.as_ref().unwrap()))]
     unsafe fn try_allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -2101,6 +2130,8 @@ impl<T: ?Sized> Rc<T> {
 impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// Allocates an `RcInner<T>` with sufficient space for an unsized inner value
     #[cfg(not(no_global_oom_handling))]
+    #[requires(ub_checks::can_dereference(ptr))]
+    #[ensures(|result| is_new_inner(*result))]
     unsafe fn allocate_for_ptr_in(ptr: *const T, alloc: &A) -> *mut RcInner<T> {
         // Allocate for the `RcInner<T>` using the given value.
         unsafe {
@@ -2138,6 +2169,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
 impl<T> Rc<[T]> {
     /// Allocates an `RcInner<[T]>` with the given length.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result) && ptr::metadata(*result) == len)]
     unsafe fn allocate_for_slice(len: usize) -> *mut RcInner<[T]> {
         unsafe {
             Self::allocate_for_layout(
@@ -2152,6 +2184,7 @@ impl<T> Rc<[T]> {
     ///
     /// Unsafe because the caller must either take ownership or bind `T: Copy`
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| result.len() == v.len() && Rc::strong_count(result) == 1)]
     unsafe fn copy_from_slice(v: &[T]) -> Rc<[T]> {
         unsafe {
             let ptr = Self::allocate_for_slice(v.len());
@@ -2164,6 +2197,8 @@ impl<T> Rc<[T]> {
     ///
     /// Behavior is undefined should the size be wrong.
     #[cfg(not(no_global_oom_handling))]
+    #[requires(iter.size_hint().0 <= len && iter.size_hint().1.is_none_or(|upper| len <= upper))]
+    #[ensures(|result| result.len() == len && Rc::strong_count(result) == 1)]
     unsafe fn from_iter_exact(iter: impl Iterator<Item = T>, len: usize) -> Rc<[T]> {
         // Panic guard while cloning T elements.
         // In the event of a panic, elements that have been written
@@ -2214,6 +2249,7 @@ impl<T, A: Allocator> Rc<[T], A> {
     /// Allocates an `RcInner<[T]>` with the given length.
     #[inline]
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result) && ptr::metadata(*result) == len)]
     unsafe fn allocate_for_slice_in(len: usize, alloc: &A) -> *mut RcInner<[T]> {
         unsafe {
             Rc::<[T]>::allocate_for_layout(
@@ -3121,6 +3157,7 @@ impl<T: ?Sized> Weak<T> {
     /// [`new`]: Weak::new
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr) || is_live_value(ptr, 0))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -3293,6 +3330,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`new`]: Weak::new
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr) || is_live_value(ptr, 0))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -3664,6 +3702,7 @@ impl<T: ?Sized, A: Allocator> Unpin for Rc<T, A> {}
 ///
 /// The pointer must point to (and have valid metadata for) a previously
 /// valid instance of T, but the T is allowed to be dropped.
+#[requires(ub_checks::can_dereference(ptr))]
 unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize {
     // Align the unsized value to the end of the RcInner.
     // Because RcInner is repr(C), it will always be the last field in memory.
@@ -3680,6 +3719,39 @@ fn data_offset_align(align: usize) -> usize {
     layout.size() + layout.padding_needed_for(align)
 }
 
+/// Whether `ptr` points to an `RcInner` that satisfies its invariant and has at least
+/// `min_strong` strong references.
+///
+/// Only used in contracts. That the allocation comes from the allocator that the caller passes
+/// along cannot be checked.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_live_inner<T: ?Sized>(ptr: *const RcInner<T>, min_strong: usize) -> bool {
+    ub_checks::can_dereference(ptr)
+        // SAFETY: we just checked that `ptr` can be dereferenced.
+        && unsafe { (*ptr).is_safe() && (*ptr).strong.get() >= min_strong }
+}
+
+/// Whether `ptr` points to the value of an `RcInner` that [`is_live_inner`], as the pointers
+/// returned by `Rc::into_raw` and `Weak::into_raw` do.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_live_value<T: ?Sized>(ptr: *const T, min_strong: usize) -> bool {
+    if !ub_checks::can_dereference(ptr) {
+        return false;
+    }
+    // SAFETY: we just checked that `ptr` points to a valid `T`.
+    let inner = ptr.wrapping_byte_sub(unsafe { data_offset(ptr) }) as *const RcInner<T>;
+    is_live_inner(inner, min_strong)
+}
+
+/// Whether `ptr` is a freshly allocated `RcInner`, with one strong and one weak reference and a
+/// value that is yet to be initialized.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_new_inner<T: ?Sized>(ptr: *const RcInner<T>) -> bool {
+    ub_checks::can_dereference(ptr)
+        // SAFETY: we just checked that `ptr` can be dereferenced.
+        && unsafe { (*ptr).strong.get() == 1 && (*ptr).weak.get() == 1 }
+}
+
 /// A uniquely owned [`Rc`].
 ///
 /// This represents an `Rc` that is known to be uniquely owned -- that is, have exactly one strong
@@ -4134,6 +4206,7 @@ impl<T: ?Sized, A: Allocator> UniqueRcUninit<T, A> {
     /// # Safety
     ///
     /// The data must have been initialized (by writing to [`Self::data_ptr()`]).
+    #[requires(ub_checks::can_dereference(self.ptr.as_ptr()))]
     unsafe fn into_rc(self) -> Rc<T, A> {
         let mut this = ManuallyDrop::new(self);
         let ptr = this.ptr;
@@ -4159,3 +4232,351 @@ impl<T: ?Sized, A: Allocator> Drop for UniqueRcUninit<T, A> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    macro_rules! generate_rc_harnesses {
+        ($ty:ty, $new:expr, $mod_name:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                fn any_rc() -> Rc<$ty> {
+                    $new
+                }
+
+                // pub unsafe fn from_raw(ptr: *const T) -> Self
+                #[kani::proof_for_contract(Rc::from_raw)]
+                pub fn check_from_raw() {
+                    let ptr = Rc::into_raw(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_raw(ptr) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // pub unsafe fn increment_strong_count(ptr: *const T)
+                #[kani::proof_for_contract(Rc::increment_strong_count)]
+                pub fn check_increment_strong_count() {
+                    let rc = any_rc();
+                    let ptr = Rc::as_ptr(&rc);
+                    unsafe { Rc::<$ty>::increment_strong_count(ptr) };
+                    assert_eq!(Rc::strong_count(&rc), 2);
+                    unsafe { Rc::<$ty>::decrement_strong_count(ptr) };
+                }
+
+                // pub unsafe fn decrement_strong_count(ptr: *const T)
+                #[kani::proof_for_contract(Rc::decrement_strong_count)]
+                pub fn check_decrement_strong_count() {
+                    let rc = any_rc();
+                    let weak = Rc::downgrade(&rc);
+                    let ptr = Rc::into_raw(rc);
+                    if kani::any() {
+                        unsafe { Rc::<$ty>::increment_strong_count(ptr) };
+                        unsafe { Rc::<$ty>::decrement_strong_count(ptr) };
+                        assert_eq!(weak.strong_count(), 1);
+                    }
+                    // Releases the last `Rc`.
+                    unsafe { Rc::<$ty>::decrement_strong_count(ptr) };
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // pub unsafe fn from_raw(ptr: *const T) -> Self
+                #[kani::proof_for_contract(Weak::from_raw)]
+                pub fn check_weak_from_raw() {
+                    let rc = any_rc();
+                    let ptr = Weak::into_raw(Rc::downgrade(&rc));
+                    // The value may already have been dropped.
+                    let rc = if kani::any() { Some(rc) } else { None };
+                    let weak = unsafe { Weak::<$ty>::from_raw(ptr) };
+                    assert_eq!(weak.upgrade().is_some(), rc.is_some());
+                }
+
+                // pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T
+                #[kani::proof_for_contract(Rc::get_mut_unchecked)]
+                pub fn check_get_mut_unchecked() {
+                    let mut rc = any_rc();
+                    let other = Rc::clone(&rc);
+                    let value = unsafe { Rc::get_mut_unchecked(&mut rc) };
+                    assert!(ptr::addr_eq(value, Rc::as_ptr(&other)));
+                }
+
+                // unsafe fn from_inner(ptr: NonNull<RcInner<T>>) -> Self
+                #[kani::proof_for_contract(Rc::from_inner)]
+                pub fn check_from_inner() {
+                    let (ptr, _) = Rc::into_inner_with_allocator(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_inner(ptr) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // unsafe fn from_ptr(ptr: *mut RcInner<T>) -> Self
+                #[kani::proof_for_contract(Rc::from_ptr)]
+                pub fn check_from_ptr() {
+                    let (ptr, _) = Rc::into_inner_with_allocator(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_ptr(ptr.as_ptr()) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // unsafe fn from_inner_in(ptr: NonNull<RcInner<T>>, alloc: A) -> Self
+                #[kani::proof_for_contract(Rc::from_inner_in)]
+                pub fn check_from_inner_in() {
+                    let (ptr, alloc) = Rc::into_inner_with_allocator(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_inner_in(ptr, alloc) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // unsafe fn from_ptr_in(ptr: *mut RcInner<T>, alloc: A) -> Self
+                #[kani::proof_for_contract(Rc::from_ptr_in)]
+                pub fn check_from_ptr_in() {
+                    let (ptr, alloc) = Rc::into_inner_with_allocator(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_ptr_in(ptr.as_ptr(), alloc) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self
+                #[kani::proof_for_contract(Rc::from_raw_in)]
+                pub fn check_from_raw_in() {
+                    let (ptr, alloc) = Rc::into_raw_with_allocator(any_rc());
+                    let rc = unsafe { Rc::<$ty>::from_raw_in(ptr, alloc) };
+                    assert_eq!(Rc::strong_count(&rc), 1);
+                }
+
+                // pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
+                #[kani::proof_for_contract(Rc::increment_strong_count_in)]
+                pub fn check_increment_strong_count_in() {
+                    let rc = any_rc();
+                    let ptr = Rc::as_ptr(&rc);
+                    unsafe { Rc::<$ty>::increment_strong_count_in(ptr, Global) };
+                    assert_eq!(Rc::strong_count(&rc), 2);
+                    unsafe { Rc::<$ty>::decrement_strong_count_in(ptr, Global) };
+                }
+
+                // pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A)
+                #[kani::proof_for_contract(Rc::decrement_strong_count_in)]
+                pub fn check_decrement_strong_count_in() {
+                    let rc = any_rc();
+                    let weak = Rc::downgrade(&rc);
+                    let (ptr, alloc) = Rc::into_raw_with_allocator(rc);
+                    // Releases the last `Rc`.
+                    unsafe { Rc::<$ty>::decrement_strong_count_in(ptr, alloc) };
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self
+                #[kani::proof_for_contract(Weak::from_raw_in)]
+                pub fn check_weak_from_raw_in() {
+                    let rc = any_rc();
+                    let (ptr, alloc) = Weak::into_raw_with_allocator(Rc::downgrade(&rc));
+                    let weak = unsafe { Weak::<$ty>::from_raw_in(ptr, alloc) };
+                    assert!(weak.upgrade().is_some_and(|upgraded| Rc::ptr_eq(&upgraded, &rc)));
+                }
+
+                // unsafe fn drop_slow(&mut self)
+                #[kani::proof_for_contract(Rc::drop_slow)]
+                pub fn check_drop_slow() {
+                    let rc = any_rc();
+                    let weak = Rc::downgrade(&rc);
+                    // Dropping the last `Rc` drops the value.
+                    drop(rc);
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize
+                #[kani::proof_for_contract(data_offset)]
+                pub fn check_data_offset() {
+                    let rc = any_rc();
+                    let offset = unsafe { data_offset(Rc::as_ptr(&rc)) };
+                    let inner = Rc::as_ptr(&rc).wrapping_byte_sub(offset);
+                    assert!(ptr::addr_eq(inner, rc.ptr.as_ptr()));
+                }
+
+                // unsafe fn allocate_for_layout(value_layout: Layout, ...) -> *mut RcInner<T>
+                #[kani::proof_for_contract(Rc::allocate_for_layout)]
+                pub fn check_allocate_for_layout() {
+                    let rc = any_rc();
+                    unsafe {
+                        let ptr = Rc::<$ty>::allocate_for_layout(
+                            Layout::for_value(&*rc),
+                            |layout| Global.allocate(layout),
+                            |mem| mem.with_metadata_of(Rc::as_ptr(&rc) as *const RcInner<$ty>),
+                        );
+                        let layout = Layout::for_value_raw(ptr);
+                        Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                    }
+                }
+
+                // unsafe fn try_allocate_for_layout(...) -> Result<*mut RcInner<T>, AllocError>
+                #[kani::proof_for_contract(Rc::try_allocate_for_layout)]
+                pub fn check_try_allocate_for_layout() {
+                    let rc = any_rc();
+                    unsafe {
+                        let result = Rc::<$ty>::try_allocate_for_layout(
+                            Layout::for_value(&*rc),
+                            |layout| {
+                                if kani::any() { Global.allocate(layout) } else { Err(AllocError) }
+                            },
+                            |mem| mem.with_metadata_of(Rc::as_ptr(&rc) as *const RcInner<$ty>),
+                        );
+                        if let Ok(ptr) = result {
+                            let layout = Layout::for_value_raw(ptr);
+                            Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                        }
+                    }
+                }
+
+                // unsafe fn allocate_for_ptr_in(ptr: *const T, alloc: &A) -> *mut RcInner<T>
+                #[kani::proof_for_contract(Rc::allocate_for_ptr_in)]
+                pub fn check_allocate_for_ptr_in() {
+                    let rc = any_rc();
+                    unsafe {
+                        let ptr = Rc::<$ty>::allocate_for_ptr_in(Rc::as_ptr(&rc), &Global);
+                        let layout = Layout::for_value_raw(ptr);
+                        Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                    }
+                }
+
+                // unsafe fn into_rc(self) -> Rc<T, A>
+                #[kani::proof_for_contract(UniqueRcUninit::into_rc)]
+                pub fn check_into_rc() {
+                    let mut rc = any_rc();
+                    let other = Rc::clone(&rc);
+                    let weak = Rc::downgrade(&rc);
+                    // Clones the value into a new allocation, since `other` shares it.
+                    Rc::make_mut(&mut rc);
+                    assert!(!Rc::ptr_eq(&rc, &other));
+                    assert!(*rc == *other);
+                    assert!(rc.inner().is_safe() && other.inner().is_safe());
+                    assert!(weak.upgrade().is_some_and(|upgraded| Rc::ptr_eq(&upgraded, &other)));
+                }
+
+                #[kani::proof]
+                pub fn check_counts() {
+                    let rc = any_rc();
+                    let clone = Rc::clone(&rc);
+                    let weak = Rc::downgrade(&rc);
+                    assert!(rc.inner().is_safe());
+                    assert_eq!(Rc::strong_count(&rc), 2);
+                    assert_eq!(Rc::weak_count(&rc), 1);
+                    drop(clone);
+                    assert!(rc.inner().is_safe());
+                    drop(rc);
+                    assert!(weak.upgrade().is_none());
+                    assert_eq!(weak.strong_count(), 0);
+                }
+            }
+        };
+    }
+
+    generate_rc_harnesses!(u32, Rc::new(kani::any()), check_sized);
+    generate_rc_harnesses!([u8], Rc::from(&kani::any::<[u8; 3]>()[..]), check_slice);
+    generate_rc_harnesses!(str, Rc::from("rc"), check_str);
+
+    // pub unsafe fn from_raw(ptr: *const T) -> Self
+    #[kani::proof_for_contract(Weak::from_raw)]
+    pub fn check_weak_from_raw_dangling() {
+        let ptr = Weak::into_raw(Weak::<u32>::new());
+        let weak = unsafe { Weak::from_raw(ptr) };
+        assert!(weak.upgrade().is_none());
+    }
+
+    // pub unsafe fn assume_init(self) -> Rc<T, A>
+    #[kani::proof_for_contract(Rc::<mem::MaybeUninit<u32>>::assume_init)]
+    pub fn check_assume_init() {
+        let value: u32 = kani::any();
+        let mut rc = Rc::<u32>::new_uninit();
+        Rc::get_mut(&mut rc).unwrap().write(value);
+        let rc = unsafe { rc.assume_init() };
+        assert_eq!(*rc, value);
+    }
+
+    // pub unsafe fn assume_init(self) -> Rc<[T], A>
+    #[kani::proof_for_contract(Rc::<[mem::MaybeUninit<u32>]>::assume_init)]
+    pub fn check_assume_init_slice() {
+        let values: [u32; 3] = kani::any();
+        let mut rc = Rc::<[u32]>::new_uninit_slice(values.len());
+        for (slot, value) in Rc::get_mut(&mut rc).unwrap().iter_mut().zip(values) {
+            slot.write(value);
+        }
+        let rc = unsafe { rc.assume_init() };
+        assert_eq!(*rc, values);
+    }
+
+    // pub unsafe fn downcast_unchecked<T: Any>(self) -> Rc<T, A>
+    #[kani::proof_for_contract(Rc::downcast_unchecked)]
+    pub fn check_downcast_unchecked() {
+        let value: u32 = kani::any();
+        let rc: Rc<dyn Any> = Rc::new(value);
+        let rc = unsafe { rc.downcast_unchecked::<u32>() };
+        assert_eq!(*rc, value);
+    }
+
+    // unsafe fn copy_from_slice(v: &[T]) -> Rc<[T]>
+    #[kani::proof_for_contract(Rc::copy_from_slice)]
+    pub fn check_copy_from_slice() {
+        let values: [u32; 3] = kani::any();
+        let len = kani::any_where(|&len: &usize| len <= values.len());
+        let rc = unsafe { Rc::copy_from_slice(&values[..len]) };
+        assert_eq!(*rc, values[..len]);
+    }
+
+    // unsafe fn from_iter_exact(iter: impl Iterator<Item = T>, len: usize) -> Rc<[T]>
+    #[kani::proof_for_contract(Rc::from_iter_exact)]
+    pub fn check_from_iter_exact() {
+        let values: [u32; 3] = kani::any();
+        let len = kani::any_where(|&len: &usize| len <= values.len());
+        let rc = unsafe { Rc::from_iter_exact(values.into_iter().take(len), len) };
+        assert_eq!(*rc, values[..len]);
+    }
+
+    // unsafe fn allocate_for_slice(len: usize) -> *mut RcInner<[T]>
+    #[kani::proof_for_contract(Rc::allocate_for_slice)]
+    pub fn check_allocate_for_slice() {
+        let len = kani::any_where(|&len: &usize| len <= 3);
+        unsafe {
+            let ptr = Rc::<[u32]>::allocate_for_slice(len);
+            Global.deallocate(NonNull::new_unchecked(ptr.cast()), Layout::for_value_raw(ptr));
+        }
+    }
+
+    // unsafe fn allocate_for_slice_in(len: usize, alloc: &A) -> *mut RcInner<[T]>
+    #[kani::proof_for_contract(Rc::allocate_for_slice_in)]
+    pub fn check_allocate_for_slice_in() {
+        let len = kani::any_where(|&len: &usize| len <= 3);
+        unsafe {
+            let ptr = Rc::<[u32]>::allocate_for_slice_in(len, &Global);
+            assert_eq!(ptr::metadata(ptr), len);
+            Global.deallocate(NonNull::new_unchecked(ptr.cast()), Layout::for_value_raw(ptr));
+        }
+    }
+
+    #[kani::proof]
+    pub fn check_new_cyclic() {
+        struct Cyclic {
+            value: u32,
+            this: Weak<Cyclic>,
+        }
+
+        let value: u32 = kani::any();
+        let rc = Rc::new_cyclic(|weak: &Weak<Cyclic>| {
+            assert!(weak.upgrade().is_none());
+            Cyclic { value, this: weak.clone() }
+        });
+        assert!(rc.inner().is_safe());
+        assert_eq!(rc.value, value);
+        assert!(rc.this.upgrade().is_some_and(|upgraded| Rc::ptr_eq(&upgraded, &rc)));
+        assert_eq!(Rc::weak_count(&rc), 1);
+    }
+
+    #[kani::proof]
+    pub fn check_unique_rc() {
+        let value: u32 = kani::any();
+        let mut unique = UniqueRc::new(value);
+        let weak = UniqueRc::downgrade(&unique);
+        assert!(weak.upgrade().is_none());
+        *unique = unique.wrapping_add(1);
+        let rc = UniqueRc::into_rc(unique);
+        assert!(rc.inner().is_safe());
+        assert_eq!(*rc, value.wrapping_add(1));
+        assert!(weak.upgrade().is_some_and(|upgraded| Rc::ptr_eq(&upgraded, &rc)));
+    }
+}
@@ -17,6 +17,8 @@ use core::hash::{Hash, Hasher};
 use core::intrinsics::abort;
 #[cfg(not(no_global_oom_handling))]
 use core::iter;
+#[cfg(kani)]
+use core::kani;
 use core::marker::{PhantomData, Unsize};
 use core::mem::{self, ManuallyDrop, align_of_val_raw};
 use core::num::NonZeroUsize;
@@ -28,8 +30,13 @@ use core::ptr::{self, NonNull};
 use core::slice::from_raw_parts_mut;
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 use core::sync::atomic::{self, Atomic};
+// Used only for contract verification.
+#[cfg(any(kani, contracts_runtime_checks))]
+use core::ub_checks::{self, Invariant};
 use core::{borrow, fmt, hint};
 
+use safety::{ensures, invariant, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{AllocError, Allocator, Global, Layout};
@@ -282,10 +289,12 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized, A: Allocator> CoerceUnsized<Arc<U, A>> fo
 impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Arc<U>> for Arc<T> {}
 
 impl<T: ?Sized> Arc<T> {
+    #[requires(is_live_inner(ptr.as_ptr(), 1))]
     unsafe fn from_inner(ptr: NonNull<ArcInner<T>>) -> Self {
         unsafe { Self::from_inner_in(ptr, Global) }
     }
 
+    #[requires(is_live_inner(ptr, 1))]
     unsafe fn from_ptr(ptr: *mut ArcInner<T>) -> Self {
         unsafe { Self::from_ptr_in(ptr, Global) }
     }
@@ -299,11 +308,13 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     }
 
     #[inline]
+    #[requires(is_live_inner(ptr.as_ptr(), 1))]
     unsafe fn from_inner_in(ptr: NonNull<ArcInner<T>>, alloc: A) -> Self {
         Self { ptr, phantom: PhantomData, alloc }
     }
 
     #[inline]
+    #[requires(is_live_inner(ptr, 1))]
     unsafe fn from_ptr_in(ptr: *mut ArcInner<T>, alloc: A) -> Self {
         unsafe { Self::from_inner_in(NonNull::new_unchecked(ptr), alloc) }
     }
@@ -367,7 +378,16 @@ impl<T: ?Sized, A: Allocator> fmt::Debug for Weak<T, A> {
 // This is repr(C) to future-proof against possible field-reordering, which
 // would interfere with otherwise safe [into|from]_raw() of transmutable
 // inner types.
+//
+// The strong pointers collectively hold one weak reference, and the allocation is
+// freed once the weak count drops to zero, so the weak count of a live `ArcInner`
+// is never zero. The `usize::MAX` sentinel is only ever set by `is_unique`, which
+// runs on behalf of a strong pointer.
 #[repr(C)]
+#[invariant({
+    let weak = self.weak.load(Relaxed);
+    weak > 0 && (weak != usize::MAX || self.strong.load(Relaxed) > 0)
+})]
 struct ArcInner<T: ?Sized> {
     strong: Atomic<usize>,
 
@@ -1353,6 +1373,7 @@ impl<T, A: Allocator> Arc<mem::MaybeUninit<T>, A> {
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
+    #[requires(ub_checks::can_dereference(Arc::as_ptr(&self) as *const T))]
     pub unsafe fn assume_init(self) -> Arc<T, A> {
         let (ptr, alloc) = Arc::into_inner_with_allocator(self);
         unsafe { Arc::from_inner_in(ptr.cast(), alloc) }
@@ -1394,6 +1415,7 @@ impl<T, A: Allocator> Arc<[mem::MaybeUninit<T>], A> {
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
+    #[requires(ub_checks::can_dereference(Arc::as_ptr(&self) as *const [T]))]
     pub unsafe fn assume_init(self) -> Arc<[T], A> {
         let (ptr, alloc) = Arc::into_inner_with_allocator(self);
         unsafe { Arc::from_ptr_in(ptr.as_ptr() as _, alloc) }
@@ -1464,6 +1486,7 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Arc::from_raw_in(ptr, Global) }
     }
@@ -1502,6 +1525,7 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Arc::increment_strong_count_in(ptr, Global) }
     }
@@ -1542,6 +1566,7 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Arc::decrement_strong_count_in(ptr, Global) }
     }
@@ -1708,6 +1733,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         unsafe {
             let offset = data_offset(ptr);
@@ -1864,6 +1890,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1913,6 +1940,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live_value(ptr, 1))]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Arc::from_raw_in(ptr, alloc)) };
     }
@@ -1929,6 +1957,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
 
     // Non-inlined part of `drop`.
     #[inline(never)]
+    #[requires(self.inner().strong.load(Relaxed) == 0 && self.inner().is_safe())]
     unsafe fn drop_slow(&mut self) {
         // Drop the weak ref collectively held by all strong references when this
         // variable goes out of scope. This ensures that the memory is deallocated
@@ -1975,6 +2004,7 @@ impl<T: ?Sized> Arc<T> {
     /// The function `mem_to_arcinner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `ArcInner<T>`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result))]
     unsafe fn allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -1993,6 +2023,7 @@ impl<T: ?Sized> Arc<T> {
     ///
     /// The function `mem_to_arcinner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `ArcInner<T>`.
+    #[ensures(|result| result.is_err() || is_new_inner(*result.as_ref().unwrap()))]
     unsafe fn try_allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -2007,6 +2038,7 @@ impl<T: ?Sized> Arc<T> {
         Ok(inner)
     }
 
+    #[ensures(|result| is_new_inner(*result))]
     unsafe fn initialize_arcinner(
         ptr: NonNull<[u8]>,
         layout: Layout,
@@ -2028,6 +2060,8 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// Allocates an `ArcInner<T>` with sufficient space for an unsized inner value.
     #[inline]
     #[cfg(not(no_global_oom_handling))]
+    #[requires(ub_checks::can_dereference(ptr))]
+    #[ensures(|result| is_new_inner(*result))]
     unsafe fn allocate_for_ptr_in(ptr: *const T, alloc: &A) -> *mut ArcInner<T> {
         // Allocate for the `ArcInner<T>` using the given value.
         unsafe {
@@ -2065,6 +2099,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
 impl<T> Arc<[T]> {
     /// Allocates an `ArcInner<[T]>` with the given length.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result) && ptr::metadata(*result) == len)]
     unsafe fn allocate_for_slice(len: usize) -> *mut ArcInner<[T]> {
         unsafe {
             Self::allocate_for_layout(
@@ -2079,6 +2114,7 @@ impl<T> Arc<[T]> {
     ///
     /// Unsafe because the caller must either take ownership or bind `T: Copy`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| result.len() == v.len() && Arc::strong_count(result) == 1)]
     unsafe fn copy_from_slice(v: &[T]) -> Arc<[T]> {
         unsafe {
             let ptr = Self::allocate_for_slice(v.len());
@@ -2093,6 +2129,8 @@ impl<T> Arc<[T]> {
     ///
     /// Behavior is undefined should the size be wrong.
     #[cfg(not(no_global_oom_handling))]
+    #[requires(iter.size_hint().0 <= len && iter.size_hint().1.is_none_or(|upper| len <= upper))]
+    #[ensures(|result| result.len() == len && Arc::strong_count(result) == 1)]
     unsafe fn from_iter_exact(iter: impl Iterator<Item = T>, len: usize) -> Arc<[T]> {
         // Panic guard while cloning T elements.
         // In the event of a panic, elements that have been written
@@ -2143,6 +2181,7 @@ impl<T, A: Allocator> Arc<[T], A> {
     /// Allocates an `ArcInner<[T]>` with the given length.
     #[inline]
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result| is_new_inner(*result) && ptr::metadata(*result) == len)]
     unsafe fn allocate_for_slice_in(len: usize, alloc: &A) -> *mut ArcInner<[T]> {
         unsafe {
             Arc::allocate_for_layout(
@@ -2520,6 +2559,8 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    // `into_inner` also calls this once the strong count has dropped to zero.
+    #[requires(is_live_inner(this.ptr.as_ptr(), 0))]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would alias with concurrent access to the reference counts (e.g. by `Weak`).
@@ -2751,6 +2792,7 @@ impl<A: Allocator> Arc<dyn Any + Send + Sync, A> {
     /// [`downcast`]: Self::downcast
     #[inline]
     #[unstable(feature = "downcast_unchecked", issue = "90850")]
+    #[requires((*self).type_id() == core::any::TypeId::of::<T>())]
     pub unsafe fn downcast_unchecked<T>(self) -> Arc<T, A>
     where
         T: Any + Send + Sync,
@@ -2861,6 +2903,7 @@ impl<T: ?Sized> Weak<T> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr) || is_live_value(ptr, 0))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Weak::from_raw_in(ptr, Global) }
     }
@@ -3032,6 +3075,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr) || is_live_value(ptr, 0))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -4020,6 +4064,7 @@ impl<T: ?Sized, A: Allocator> Unpin for Arc<T, A> {}
 ///
 /// The pointer must point to (and have valid metadata for) a previously
 /// valid instance of T, but the T is allowed to be dropped.
+#[requires(ub_checks::can_dereference(ptr))]
 unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize {
     // Align the unsized value to the end of the ArcInner.
     // Because RcInner is repr(C), it will always be the last field in memory.
@@ -4036,6 +4081,39 @@ fn data_offset_align(align: usize) -> usize {
     layout.size() + layout.padding_needed_for(align)
 }
 
+/// Whether `ptr` points to an `ArcInner` that satisfies its invariant and has at least
+/// `min_strong` strong references.
+///
+/// Only used in contracts. That the allocation comes from the allocator that the caller passes
+/// along cannot be checked.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_live_inner<T: ?Sized>(ptr: *const ArcInner<T>, min_strong: usize) -> bool {
+    ub_checks::can_dereference(ptr)
+        // SAFETY: we just checked that `ptr` can be dereferenced.
+        && unsafe { (*ptr).is_safe() && (*ptr).strong.load(Relaxed) >= min_strong }
+}
+
+/// Whether `ptr` points to the value of an `ArcInner` that [`is_live_inner`], as the pointers
+/// returned by `Arc::into_raw` and `Weak::into_raw` do.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_live_value<T: ?Sized>(ptr: *const T, min_strong: usize) -> bool {
+    if !ub_checks::can_dereference(ptr) {
+        return false;
+    }
+    // SAFETY: we just checked that `ptr` points to a valid `T`.
+    let inner = ptr.wrapping_byte_sub(unsafe { data_offset(ptr) }) as *const ArcInner<T>;
+    is_live_inner(inner, min_strong)
+}
+
+/// Whether `ptr` is a freshly allocated `ArcInner`, with one strong and one weak reference and a
+/// value that is yet to be initialized.
+#[cfg(any(kani, contracts_runtime_checks))]
+fn is_new_inner<T: ?Sized>(ptr: *const ArcInner<T>) -> bool {
+    ub_checks::can_dereference(ptr)
+        // SAFETY: we just checked that `ptr` can be dereferenced.
+        && unsafe { (*ptr).strong.load(Relaxed) == 1 && (*ptr).weak.load(Relaxed) == 1 }
+}
+
 /// A unique owning pointer to an [`ArcInner`] **that does not imply the contents are initialized,**
 /// but will deallocate it (without dropping the value) when dropped.
 ///
@@ -4073,6 +4151,7 @@ impl<T: ?Sized, A: Allocator> UniqueArcUninit<T, A> {
     /// # Safety
     ///
     /// The data must have been initialized (by writing to [`Self::data_ptr()`]).
+    #[requires(ub_checks::can_dereference(self.ptr.as_ptr()))]
     unsafe fn into_arc(self) -> Arc<T, A> {
         let mut this = ManuallyDrop::new(self);
         let ptr = this.ptr.as_ptr();
@@ -4529,3 +4608,365 @@ unsafe impl<#[may_dangle] T: ?Sized, A: Allocator> Drop for UniqueArc<T, A> {
         unsafe { ptr::drop_in_place(&mut (*self.ptr.as_ptr()).data) };
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    macro_rules! generate_arc_harnesses {
+        ($ty:ty, $new:expr, $mod_name:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                fn any_arc() -> Arc<$ty> {
+                    $new
+                }
+
+                // pub unsafe fn from_raw(ptr: *const T) -> Self
+                #[kani::proof_for_contract(Arc::from_raw)]
+                pub fn check_from_raw() {
+                    let ptr = Arc::into_raw(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_raw(ptr) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // pub unsafe fn increment_strong_count(ptr: *const T)
+                #[kani::proof_for_contract(Arc::increment_strong_count)]
+                pub fn check_increment_strong_count() {
+                    let arc = any_arc();
+                    let ptr = Arc::as_ptr(&arc);
+                    unsafe { Arc::<$ty>::increment_strong_count(ptr) };
+                    assert_eq!(Arc::strong_count(&arc), 2);
+                    unsafe { Arc::<$ty>::decrement_strong_count(ptr) };
+                }
+
+                // pub unsafe fn decrement_strong_count(ptr: *const T)
+                #[kani::proof_for_contract(Arc::decrement_strong_count)]
+                pub fn check_decrement_strong_count() {
+                    let arc = any_arc();
+                    let weak = Arc::downgrade(&arc);
+                    let ptr = Arc::into_raw(arc);
+                    if kani::any() {
+                        unsafe { Arc::<$ty>::increment_strong_count(ptr) };
+                        unsafe { Arc::<$ty>::decrement_strong_count(ptr) };
+                        assert_eq!(weak.strong_count(), 1);
+                    }
+                    // Releases the last `Arc`.
+                    unsafe { Arc::<$ty>::decrement_strong_count(ptr) };
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // pub unsafe fn from_raw(ptr: *const T) -> Self
+                #[kani::proof_for_contract(Weak::from_raw)]
+                pub fn check_weak_from_raw() {
+                    let arc = any_arc();
+                    let ptr = Weak::into_raw(Arc::downgrade(&arc));
+                    // The value may already have been dropped.
+                    let arc = if kani::any() { Some(arc) } else { None };
+                    let weak = unsafe { Weak::<$ty>::from_raw(ptr) };
+                    assert_eq!(weak.upgrade().is_some(), arc.is_some());
+                }
+
+                // pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T
+                #[kani::proof_for_contract(Arc::get_mut_unchecked)]
+                pub fn check_get_mut_unchecked() {
+                    let mut arc = any_arc();
+                    let other = Arc::clone(&arc);
+                    let value = unsafe { Arc::get_mut_unchecked(&mut arc) };
+                    assert!(ptr::addr_eq(value, Arc::as_ptr(&other)));
+                }
+
+                // unsafe fn from_inner(ptr: NonNull<ArcInner<T>>) -> Self
+                #[kani::proof_for_contract(Arc::from_inner)]
+                pub fn check_from_inner() {
+                    let (ptr, _) = Arc::into_inner_with_allocator(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_inner(ptr) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // unsafe fn from_ptr(ptr: *mut ArcInner<T>) -> Self
+                #[kani::proof_for_contract(Arc::from_ptr)]
+                pub fn check_from_ptr() {
+                    let (ptr, _) = Arc::into_inner_with_allocator(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_ptr(ptr.as_ptr()) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // unsafe fn from_inner_in(ptr: NonNull<ArcInner<T>>, alloc: A) -> Self
+                #[kani::proof_for_contract(Arc::from_inner_in)]
+                pub fn check_from_inner_in() {
+                    let (ptr, alloc) = Arc::into_inner_with_allocator(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_inner_in(ptr, alloc) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // unsafe fn from_ptr_in(ptr: *mut ArcInner<T>, alloc: A) -> Self
+                #[kani::proof_for_contract(Arc::from_ptr_in)]
+                pub fn check_from_ptr_in() {
+                    let (ptr, alloc) = Arc::into_inner_with_allocator(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_ptr_in(ptr.as_ptr(), alloc) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self
+                #[kani::proof_for_contract(Arc::from_raw_in)]
+                pub fn check_from_raw_in() {
+                    let (ptr, alloc) = Arc::into_raw_with_allocator(any_arc());
+                    let arc = unsafe { Arc::<$ty>::from_raw_in(ptr, alloc) };
+                    assert_eq!(Arc::strong_count(&arc), 1);
+                }
+
+                // pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
+                #[kani::proof_for_contract(Arc::increment_strong_count_in)]
+                pub fn check_increment_strong_count_in() {
+                    let arc = any_arc();
+                    let ptr = Arc::as_ptr(&arc);
+                    unsafe { Arc::<$ty>::increment_strong_count_in(ptr, Global) };
+                    assert_eq!(Arc::strong_count(&arc), 2);
+                    unsafe { Arc::<$ty>::decrement_strong_count_in(ptr, Global) };
+                }
+
+                // pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A)
+                #[kani::proof_for_contract(Arc::decrement_strong_count_in)]
+                pub fn check_decrement_strong_count_in() {
+                    let arc = any_arc();
+                    let weak = Arc::downgrade(&arc);
+                    let (ptr, alloc) = Arc::into_raw_with_allocator(arc);
+                    // Releases the last `Arc`.
+                    unsafe { Arc::<$ty>::decrement_strong_count_in(ptr, alloc) };
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self
+                #[kani::proof_for_contract(Weak::from_raw_in)]
+                pub fn check_weak_from_raw_in() {
+                    let arc = any_arc();
+                    let (ptr, alloc) = Weak::into_raw_with_allocator(Arc::downgrade(&arc));
+                    let weak = unsafe { Weak::<$ty>::from_raw_in(ptr, alloc) };
+                    assert!(weak.upgrade().is_some_and(|upgraded| Arc::ptr_eq(&upgraded, &arc)));
+                }
+
+                // unsafe fn drop_slow(&mut self)
+                #[kani::proof_for_contract(Arc::drop_slow)]
+                pub fn check_drop_slow() {
+                    let arc = any_arc();
+                    let weak = Arc::downgrade(&arc);
+                    // Dropping the last `Arc` drops the value.
+                    drop(arc);
+                    assert!(weak.upgrade().is_none());
+                }
+
+                // unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize
+                #[kani::proof_for_contract(data_offset)]
+                pub fn check_data_offset() {
+                    let arc = any_arc();
+                    let offset = unsafe { data_offset(Arc::as_ptr(&arc)) };
+                    let inner = Arc::as_ptr(&arc).wrapping_byte_sub(offset);
+                    assert!(ptr::addr_eq(inner, arc.ptr.as_ptr()));
+                }
+
+                // unsafe fn allocate_for_layout(value_layout: Layout, ...) -> *mut ArcInner<T>
+                #[kani::proof_for_contract(Arc::allocate_for_layout)]
+                pub fn check_allocate_for_layout() {
+                    let arc = any_arc();
+                    unsafe {
+                        let ptr = Arc::<$ty>::allocate_for_layout(
+                            Layout::for_value(&*arc),
+                            |layout| Global.allocate(layout),
+                            |mem| mem.with_metadata_of(Arc::as_ptr(&arc) as *const ArcInner<$ty>),
+                        );
+                        let layout = Layout::for_value_raw(ptr);
+                        Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                    }
+                }
+
+                // unsafe fn try_allocate_for_layout(...) -> Result<*mut ArcInner<T>, AllocError>
+                #[kani::proof_for_contract(Arc::try_allocate_for_layout)]
+                pub fn check_try_allocate_for_layout() {
+                    let arc = any_arc();
+                    unsafe {
+                        let result = Arc::<$ty>::try_allocate_for_layout(
+                            Layout::for_value(&*arc),
+                            |layout| {
+                                if kani::any() { Global.allocate(layout) } else { Err(AllocError) }
+                            },
+                            |mem| mem.with_metadata_of(Arc::as_ptr(&arc) as *const ArcInner<$ty>),
+                        );
+                        if let Ok(ptr) = result {
+                            let layout = Layout::for_value_raw(ptr);
+                            Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                        }
+                    }
+                }
+
+                // unsafe fn initialize_arcinner(ptr: NonNull<[u8]>, ...) -> *mut ArcInner<T>
+                #[kani::proof_for_contract(Arc::initialize_arcinner)]
+                pub fn check_initialize_arcinner() {
+                    let arc = any_arc();
+                    let layout = arcinner_layout_for_value_layout(Layout::for_value(&*arc));
+                    unsafe {
+                        let mem = Global.allocate(layout).unwrap();
+                        let ptr = Arc::<$ty>::initialize_arcinner(mem, layout, |mem| {
+                            mem.with_metadata_of(Arc::as_ptr(&arc) as *const ArcInner<$ty>)
+                        });
+                        Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                    }
+                }
+
+                // unsafe fn allocate_for_ptr_in(ptr: *const T, alloc: &A) -> *mut ArcInner<T>
+                #[kani::proof_for_contract(Arc::allocate_for_ptr_in)]
+                pub fn check_allocate_for_ptr_in() {
+                    let arc = any_arc();
+                    unsafe {
+                        let ptr = Arc::<$ty>::allocate_for_ptr_in(Arc::as_ptr(&arc), &Global);
+                        let layout = Layout::for_value_raw(ptr);
+                        Global.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                    }
+                }
+
+                // unsafe fn into_arc(self) -> Arc<T, A>
+                #[kani::proof_for_contract(UniqueArcUninit::into_arc)]
+                pub fn check_into_arc() {
+                    let mut arc = any_arc();
+                    let other = Arc::clone(&arc);
+                    let weak = Arc::downgrade(&arc);
+                    // Clones the value into a new allocation, since `other` shares it.
+                    Arc::make_mut(&mut arc);
+                    assert!(!Arc::ptr_eq(&arc, &other));
+                    assert!(*arc == *other);
+                    assert!(arc.inner().is_safe() && other.inner().is_safe());
+                    assert!(weak.upgrade().is_some_and(|upgraded| Arc::ptr_eq(&upgraded, &other)));
+                }
+
+                #[kani::proof]
+                pub fn check_counts() {
+                    let arc = any_arc();
+                    let clone = Arc::clone(&arc);
+                    let weak = Arc::downgrade(&arc);
+                    assert!(arc.inner().is_safe());
+                    assert_eq!(Arc::strong_count(&arc), 2);
+                    assert_eq!(Arc::weak_count(&arc), 1);
+                    drop(clone);
+                    assert!(arc.inner().is_safe());
+                    drop(arc);
+                    assert!(weak.upgrade().is_none());
+                    assert_eq!(weak.strong_count(), 0);
+                }
+            }
+        };
+    }
+
+    generate_arc_harnesses!(u32, Arc::new(kani::any()), check_sized);
+    generate_arc_harnesses!([u8], Arc::from(&kani::any::<[u8; 3]>()[..]), check_slice);
+    generate_arc_harnesses!(str, Arc::from("arc"), check_str);
+
+    // pub unsafe fn from_raw(ptr: *const T) -> Self
+    #[kani::proof_for_contract(Weak::from_raw)]
+    pub fn check_weak_from_raw_dangling() {
+        let ptr = Weak::into_raw(Weak::<u32>::new());
+        let weak = unsafe { Weak::from_raw(ptr) };
+        assert!(weak.upgrade().is_none());
+    }
+
+    // pub unsafe fn assume_init(self) -> Arc<T, A>
+    #[kani::proof_for_contract(Arc::<mem::MaybeUninit<u32>>::assume_init)]
+    pub fn check_assume_init() {
+        let value: u32 = kani::any();
+        let mut arc = Arc::<u32>::new_uninit();
+        Arc::get_mut(&mut arc).unwrap().write(value);
+        let arc = unsafe { arc.assume_init() };
+        assert_eq!(*arc, value);
+    }
+
+    // pub unsafe fn assume_init(self) -> Arc<[T], A>
+    #[kani::proof_for_contract(Arc::<[mem::MaybeUninit<u32>]>::assume_init)]
+    pub fn check_assume_init_slice() {
+        let values: [u32; 3] = kani::any();
+        let mut arc = Arc::<[u32]>::new_uninit_slice(values.len());
+        for (slot, value) in Arc::get_mut(&mut arc).unwrap().iter_mut().zip(values) {
+            slot.write(value);
+        }
+        let arc = unsafe { arc.assume_init() };
+        assert_eq!(*arc, values);
+    }
+
+    // pub unsafe fn downcast_unchecked<T: Any>(self) -> Arc<T, A>
+    #[kani::proof_for_contract(Arc::downcast_unchecked)]
+    pub fn check_downcast_unchecked() {
+        let value: u32 = kani::any();
+        let arc: Arc<dyn Any + Send + Sync> = Arc::new(value);
+        let arc = unsafe { arc.downcast_unchecked::<u32>() };
+        assert_eq!(*arc, value);
+    }
+
+    // unsafe fn copy_from_slice(v: &[T]) -> Arc<[T]>
+    #[kani::proof_for_contract(Arc::copy_from_slice)]
+    pub fn check_copy_from_slice() {
+        let values: [u32; 3] = kani::any();
+        let len = kani::any_where(|&len: &usize| len <= values.len());
+        let arc = unsafe { Arc::copy_from_slice(&values[..len]) };
+        assert_eq!(*arc, values[..len]);
+    }
+
+    // unsafe fn from_iter_exact(iter: impl Iterator<Item = T>, len: usize) -> Arc<[T]>
+    #[kani::proof_for_contract(Arc::from_iter_exact)]
+    pub fn check_from_iter_exact() {
+        let values: [u32; 3] = kani::any();
+        let len = kani::any_where(|&len: &usize| len <= values.len());
+        let arc = unsafe { Arc::from_iter_exact(values.into_iter().take(len), len) };
+        assert_eq!(*arc, values[..len]);
+    }
+
+    // unsafe fn allocate_for_slice(len: usize) -> *mut ArcInner<[T]>
+    #[kani::proof_for_contract(Arc::allocate_for_slice)]
+    pub fn check_allocate_for_slice() {
+        let len = kani::any_where(|&len: &usize| len <= 3);
+        unsafe {
+            let ptr = Arc::<[u32]>::allocate_for_slice(len);
+            Global.deallocate(NonNull::new_unchecked(ptr.cast()), Layout::for_value_raw(ptr));
+        }
+    }
+
+    // unsafe fn allocate_for_slice_in(len: usize, alloc: &A) -> *mut ArcInner<[T]>
+    #[kani::proof_for_contract(Arc::allocate_for_slice_in)]
+    pub fn check_allocate_for_slice_in() {
+        let len = kani::any_where(|&len: &usize| len <= 3);
+        unsafe {
+            let ptr = Arc::<[u32]>::allocate_for_slice_in(len, &Global);
+            assert_eq!(ptr::metadata(ptr), len);
+            Global.deallocate(NonNull::new_unchecked(ptr.cast()), Layout::for_value_raw(ptr));
+        }
+    }
+
+    #[kani::proof]
+    pub fn check_new_cyclic() {
+        struct Cyclic {
+            value: u32,
+            this: Weak<Cyclic>,
+        }
+
+        let value: u32 = kani::any();
+        let arc = Arc::new_cyclic(|weak: &Weak<Cyclic>| {
+            assert!(weak.upgrade().is_none());
+            Cyclic { value, this: weak.clone() }
+        });
+        assert!(arc.inner().is_safe());
+        assert_eq!(arc.value, value);
+        assert!(arc.this.upgrade().is_some_and(|upgraded| Arc::ptr_eq(&upgraded, &arc)));
+        assert_eq!(Arc::weak_count(&arc), 1);
+    }
+
+    #[kani::proof]
+    pub fn check_unique_arc() {
+        let value: u32 = kani::any();
+        let mut unique = UniqueArc::new(value);
+        let weak = UniqueArc::downgrade(&unique);
+        assert!(weak.upgrade().is_none());
+        *unique = unique.wrapping_add(1);
+        let arc = UniqueArc::into_arc(unique);
+        assert!(arc.inner().is_safe());
+        assert_eq!(*arc, value.wrapping_add(1));
+        assert!(weak.upgrade().is_some_and(|upgraded| Arc::ptr_eq(&upgraded, &arc)));
+    }
+}
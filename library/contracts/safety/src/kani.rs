@@ -1,6 +1,8 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, ItemFn, Stmt};
+use syn::{parse_macro_input, Stmt};
+
+use crate::quantifiers::Quantifier;
 
 pub(crate) fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
     rewrite_attr(attr, item, "requires")
@@ -10,10 +12,32 @@ pub(crate) fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     rewrite_attr(attr, item, "ensures")
 }
 
+pub(crate) fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
+    rewrite_attr(attr, item, "modifies")
+}
+
 pub(crate) fn loop_invariant(attr: TokenStream, stmt: TokenStream) -> TokenStream {
     rewrite_stmt_attr(attr, stmt, "loop_invariant")
 }
 
+pub(crate) fn loop_modifies(attr: TokenStream, stmt: TokenStream) -> TokenStream {
+    rewrite_stmt_attr(attr, stmt, "loop_modifies")
+}
+
+pub(crate) fn forall(input: TokenStream) -> TokenStream {
+    rewrite_quantifier(input, "forall")
+}
+
+pub(crate) fn exists(input: TokenStream) -> TokenStream {
+    rewrite_quantifier(input, "exists")
+}
+
+fn rewrite_quantifier(input: TokenStream, name: &str) -> TokenStream {
+    let Quantifier { var, start, end, pred } = parse_macro_input!(input as Quantifier);
+    let quantifier = format_ident!("{}", name);
+    quote!(kani_core::#quantifier!(|#var in (#start, #end)| #pred)).into()
+}
+
 fn rewrite_stmt_attr(attr: TokenStream, stmt_stream: TokenStream, name: &str) -> TokenStream {
     let args = proc_macro2::TokenStream::from(attr);
     let stmt = parse_macro_input!(stmt_stream as Stmt);
@@ -27,7 +51,7 @@ fn rewrite_stmt_attr(attr: TokenStream, stmt_stream: TokenStream, name: &str) ->
 
 fn rewrite_attr(attr: TokenStream, item: TokenStream, name: &str) -> TokenStream {
     let args = proc_macro2::TokenStream::from(attr);
-    let fn_item = parse_macro_input!(item as ItemFn);
+    let fn_item = parse_macro_input!(item with crate::traits::parse_contract_fn);
     let attribute = format_ident!("{}", name);
     quote!(
         #[kani_core::#attribute(#args)]
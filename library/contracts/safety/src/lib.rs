@@ -2,11 +2,12 @@
 //! Each tool should implement their own version in a separate module of this crate.
 
 use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
-use quote::{format_ident, quote, quote_spanned};
+use proc_macro_error::{abort, proc_macro_error};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
-    parse_macro_input, parse_quote, spanned::Spanned, Data, DataEnum, DeriveInput, Fields,
-    GenericParam, Generics, Ident, Index, ItemStruct,
+    parse_macro_input, parse_quote, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Expr,
+    Fields, GenericParam, Generics, Ident, ImplItem, Index, ItemImpl, ItemStruct, ReturnType,
+    Visibility,
 };
 
 #[cfg(kani_host)]
@@ -17,6 +18,9 @@ mod tool;
 #[path = "runtime.rs"]
 mod tool;
 
+mod quantifiers;
+mod traits;
+
 /// Expands the `#[invariant(...)]` attribute macro.
 /// The macro expands to an implementation of the `is_safe` method for the `Invariant` trait.
 /// This attribute is only supported for structs.
@@ -63,7 +67,7 @@ pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Expands the derive macro for the Invariant trait.
 /// The macro expands to an implementation of the `is_safe` method for the `Invariant` trait.
-/// This macro is only supported for structs and enums.
+/// This macro is supported for structs and enums, and for unions with an explicit invariant.
 ///
 /// # Example
 ///
@@ -107,26 +111,101 @@ pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///    }
 /// }
 /// ```
+/// Variants with named fields are handled the same way, binding each field by its name.
+///
+/// The derived checks can be refined with the `safety_invariant` helper attribute:
+/// `#[safety_invariant(skip)]` on a field omits its `is_safe()` call, and
+/// `#[safety_invariant(<predicate>)]` on a field or on the type adds a predicate to the
+/// invariant. The helper attribute was previously named `invariant`, so `#[invariant(skip)]` is
+/// now spelled `#[safety_invariant(skip)]`.
+///
+/// ```ignore
+/// #[derive(Invariant)]
+/// #[safety_invariant(self.len <= self.cap)]
+/// struct Buffer {
+///     #[safety_invariant(skip)]
+///     ptr: *mut u8,
+///     len: usize,
+///     cap: usize,
+/// }
+/// ```
+///
+/// expands to:
+/// ```ignore
+/// impl core::ub_checks::Invariant for Buffer {
+///   fn is_safe(&self) -> bool {
+///     self.len.is_safe() && self.cap.is_safe() && (self.len <= self.cap)
+///   }
+/// }
+/// ```
+/// In enum variants, the predicates of a field refer to the fields by their binding name.
+///
+/// The fields of a union cannot be inspected without knowing which one is active, so the
+/// invariant of a union must be provided explicitly with the `safety_invariant` attribute:
+///
+/// ```ignore
+/// #[derive(Invariant)]
+/// #[safety_invariant(unsafe { self.value.is_safe() })]
+/// union MyUnion {
+///     value: u32,
+///     bytes: [u8; 4],
+/// }
+/// ```
+///
+/// expands to:
+/// ```ignore
+/// impl core::ub_checks::Invariant for MyUnion {
+///   fn is_safe(&self) -> bool {
+///     unsafe { self.value.is_safe() }
+///   }
+/// }
+/// ```
 /// For more information on the Invariant trait, see its documentation in core::ub_checks.
 #[proc_macro_error]
-#[proc_macro_derive(Invariant)]
+#[proc_macro_derive(Invariant, attributes(safety_invariant))]
 pub fn derive_invariant(item: TokenStream) -> TokenStream {
     let derive_item = parse_macro_input!(item as DeriveInput);
     let item_name = &derive_item.ident;
+    let item_attrs = InvariantAttrs::parse(&derive_item.attrs);
+    if item_attrs.skip {
+        abort!(item_name, "`skip` can only be used on fields");
+    }
+    let item_predicates = &item_attrs.predicates;
     let safe_body = match derive_item.data {
         Data::Struct(struct_data) => {
-            safe_body(&struct_data.fields)
+            let field_checks = safe_body(&struct_data.fields);
+            let item_checks = item_predicates.iter().map(|pred| quote! { (#pred) });
+            conjunction(field_checks.into_iter().chain(item_checks))
+        },
+        Data::Enum(enum_data) if enum_data.variants.is_empty() => {
+            // There is no value of an empty enum to check.
+            quote! { match *self {} }
         },
         Data::Enum(enum_data) => {
             let variant_checks = variant_checks(enum_data, item_name);
-
-            quote! {
+            let match_checks = quote! {
                 match self {
                     #(#variant_checks),*
                 }
+            };
+
+            if item_predicates.is_empty() {
+                match_checks
+            } else {
+                quote! { (#match_checks) #(&& (#item_predicates))* }
             }
         },
-        Data::Union(..) => unimplemented!("Attempted to derive Invariant on a union; Invariant can only be derived for structs and enums."),
+        Data::Union(union_data) => {
+            if item_predicates.is_empty() {
+                abort!(
+                    union_data.union_token,
+                    "cannot derive `Invariant` for union `{}` without an explicit invariant",
+                    item_name;
+                    help = "add `#[safety_invariant(<predicate>)]` to the union"
+                );
+            }
+            conjunction(item_predicates.iter().map(|pred| quote! { (#pred) }))
+        },
     };
 
     // Add a bound `T: Invariant` to every type parameter T.
@@ -152,18 +231,208 @@ pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
     tool::requires(attr, item)
 }
 
+/// Specifies a postcondition of a function as a closure that takes a reference to the result.
+///
+/// The postcondition may refer to the value of an expression before the function is executed
+/// with `old(expr)`, for example:
+///
+/// ```ignore
+/// #[ensures(|_| self.len() == old(self.len()) + 1)]
+/// pub fn push(&mut self, value: T) { ... }
+/// ```
+///
+/// Kani supports `old` natively, while the runtime checks evaluate and store `expr` right after
+/// the preconditions are checked.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     tool::ensures(attr, item)
 }
 
+/// Specifies the write set of a function: the memory locations that it may modify, given as
+/// pointers or references to them.
+///
+/// ```ignore
+/// #[requires(ub_checks::can_write(x) && ub_checks::can_write(y))]
+/// #[modifies(x, y)]
+/// pub unsafe fn swap<T>(x: *mut T, y: *mut T) { ... }
+/// ```
+///
+/// Kani checks that the function does not write anywhere else, which is required to soundly
+/// replace the function by its contract with `stub_verified`. The write set is not checked at
+/// runtime.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
+    tool::modifies(attr, item)
+}
+
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn loop_invariant(attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
     tool::loop_invariant(attr, stmt_stream)
 }
 
+/// Specifies the write set of a loop, like [`macro@modifies`] does for a function.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn loop_modifies(attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
+    tool::loop_modifies(attr, stmt_stream)
+}
+
+/// Checks that a predicate holds for every integer of a bounded range.
+///
+/// The range must be half-open, and a bitwise or in its bounds must be parenthesized. Kani
+/// verifies it as a quantified formula, while at runtime it is evaluated by iterating over the
+/// range.
+///
+/// # Example
+///
+/// ```ignore
+/// #[requires(forall!(|i in 0..len| unsafe { *ptr.add(i) } != 0))]
+/// pub unsafe fn all_non_zero(ptr: *const u8, len: usize) -> bool { ... }
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn forall(input: TokenStream) -> TokenStream {
+    tool::forall(input)
+}
+
+/// Checks that a predicate holds for some integer of a bounded range, see [`forall!`].
+#[proc_macro_error]
+#[proc_macro]
+pub fn exists(input: TokenStream) -> TokenStream {
+    tool::exists(input)
+}
+
+/// Checks the type invariant at the boundary of the safe methods of an `impl` block.
+///
+/// Every safe method of the block that is public, or that implements a trait method, gets the
+/// following contracts, which are then handled like any other contract:
+///  - if it has a `self` receiver, `self` must be safe when the method is called;
+///  - if it takes `&self` or `&mut self`, `self` must still be safe when the method returns;
+///  - if it returns `Self`, the returned value must be safe.
+///
+/// Unsafe methods are not checked, since their callers may temporarily break the invariant.
+///
+/// # Example
+///
+/// ```ignore
+/// #[check_invariant]
+/// impl Square {
+///     pub fn grow(&mut self) {
+///         self.width += 1;
+///         self.height += 1;
+///     }
+/// }
+/// ```
+///
+/// expands to:
+/// ```ignore
+/// impl Square {
+///     #[safety::requires({ use core::ub_checks::Invariant as _; self.is_safe() })]
+///     #[safety::ensures(|_| { use core::ub_checks::Invariant as _; self.is_safe() })]
+///     pub fn grow(&mut self) {
+///         self.width += 1;
+///         self.height += 1;
+///     }
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn check_invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        abort!(proc_macro2::TokenStream::from(attr), "`check_invariant` does not take arguments");
+    }
+    let mut impl_item = parse_macro_input!(item as ItemImpl);
+    let is_trait_impl = impl_item.trait_.is_some();
+    let self_ty = impl_item.self_ty.to_token_stream().to_string();
+    let is_safe = quote! { { use ::core::ub_checks::Invariant as _; self.is_safe() } };
+    let result_is_safe = quote! { { use ::core::ub_checks::Invariant as _; result.is_safe() } };
+
+    for impl_fn in impl_item.items.iter_mut().filter_map(|item| match item {
+        ImplItem::Fn(impl_fn) => Some(impl_fn),
+        _ => None,
+    }) {
+        let sig = &impl_fn.sig;
+        let is_public = is_trait_impl || matches!(impl_fn.vis, Visibility::Public(_));
+        if !is_public || sig.unsafety.is_some() {
+            continue;
+        }
+        let mut contracts: Vec<Attribute> = Vec::new();
+        if let Some(receiver) = sig.receiver() {
+            contracts.push(parse_quote!(#[::safety::requires(#is_safe)]));
+            if receiver.reference.is_some() {
+                contracts.push(parse_quote!(#[::safety::ensures(|_| #is_safe)]));
+            }
+        }
+        if let ReturnType::Type(_, ty) = &sig.output {
+            let ty = ty.to_token_stream().to_string();
+            if ty == "Self" || ty == self_ty {
+                contracts.push(parse_quote!(#[::safety::ensures(|result: &Self| #result_is_safe)]));
+            }
+        }
+        contracts.append(&mut impl_fn.attrs);
+        impl_fn.attrs = contracts;
+    }
+    impl_item.into_token_stream().into()
+}
+
+/// Declares contracts on the methods of a trait, which are then checked for every impl.
+///
+/// On a trait, the `requires`, `ensures` and `modifies` attributes of its methods are collected
+/// and removed from the required methods, which have no body to check. On a trait impl, the
+/// contracts of the trait are attached to the methods of the impl. The arguments of the trait
+/// methods are renamed to the arguments of the impl methods. Methods generated by a macro inside
+/// an impl are not visible to the attribute, so they can be annotated individually with the path
+/// of the trait.
+///
+/// The contracts are transported by a hidden `macro_rules!` macro defined next to the trait, so
+/// the impls must be in the same crate, after the trait or in a module where the trait path
+/// also resolves that macro.
+///
+/// # Example
+///
+/// ```ignore
+/// #[trait_contracts]
+/// pub trait Step {
+///     #[requires(Step::forward_checked(start.clone(), count).is_some())]
+///     unsafe fn forward_unchecked(start: Self, count: usize) -> Self;
+/// }
+///
+/// #[trait_contracts]
+/// impl Step for u8 {
+///     unsafe fn forward_unchecked(start: Self, n: usize) -> Self { ... }
+/// }
+///
+/// impl Step for u16 {
+///     #[trait_contracts(Step)]
+///     unsafe fn forward_unchecked(start: Self, n: usize) -> Self { ... }
+/// }
+/// ```
+///
+/// expands to:
+/// ```ignore
+/// impl Step for u8 {
+///     #[safety::requires(Step::forward_checked(start.clone(), n).is_some())]
+///     unsafe fn forward_unchecked(start: Self, n: usize) -> Self { ... }
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn trait_contracts(attr: TokenStream, item: TokenStream) -> TokenStream {
+    traits::trait_contracts(attr, item)
+}
+
+/// Implementation detail of `trait_contracts`, which attaches the contracts of a trait method to
+/// the method of an impl.
+#[doc(hidden)]
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn __trait_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    traits::trait_contract(attr, item)
+}
+
 /// Add a bound `T: Invariant` to every type parameter T.
 fn add_trait_bound_invariant(mut generics: Generics) -> Generics {
     generics.params.iter_mut().for_each(|param| {
@@ -176,6 +445,56 @@ fn add_trait_bound_invariant(mut generics: Generics) -> Generics {
     generics
 }
 
+/// The options given to `derive(Invariant)` through the `safety_invariant` helper attributes of
+/// an item or a field.
+#[derive(Default)]
+struct InvariantAttrs {
+    /// Whether the field should not be checked with `is_safe()`.
+    skip: bool,
+    /// Additional predicates that must hold.
+    predicates: Vec<Expr>,
+}
+
+impl InvariantAttrs {
+    fn parse(attrs: &[Attribute]) -> Self {
+        let mut result = InvariantAttrs::default();
+        let helper_attrs = attrs.iter().filter(|attr| attr.path().is_ident("safety_invariant"));
+        for attr in helper_attrs {
+            match attr.parse_args::<Expr>() {
+                Ok(Expr::Path(path)) if path.path.is_ident("skip") => result.skip = true,
+                Ok(predicate) => result.predicates.push(predicate),
+                Err(err) => abort!(err.span(), "invalid invariant predicate: {}", err),
+            }
+        }
+        result
+    }
+}
+
+/// Combine the checks with `&&`, or return `true` if there is none.
+fn conjunction(
+    checks: impl IntoIterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let checks: Vec<_> = checks.into_iter().collect();
+    if checks.is_empty() {
+        quote! { true }
+    } else {
+        quote! { #( #checks )&&* }
+    }
+}
+
+/// Generate the checks for the fields of an enum variant, that are bound to `names`.
+fn bound_field_checks(
+    fields: &Fields,
+    names: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let checks = fields.iter().zip(names).flat_map(|(field, name)| {
+        let attrs = InvariantAttrs::parse(&field.attrs);
+        let safe_call = (!attrs.skip).then(|| quote_spanned! {field.span()=> #name.is_safe() });
+        safe_call.into_iter().chain(attrs.predicates.into_iter().map(|pred| quote! { (#pred) }))
+    });
+    conjunction(checks)
+}
+
 /// Generate safety checks for each variant of an enum
 fn variant_checks(enum_data: DataEnum, item_name: &Ident) -> Vec<proc_macro2::TokenStream> {
     enum_data
@@ -189,18 +508,14 @@ fn variant_checks(enum_data: DataEnum, item_name: &Ident) -> Vec<proc_macro2::To
                         .unnamed
                         .iter()
                         .enumerate()
-                        .map(|(i, _)| format_ident!("field{}", i + 1))
-                        .collect();
-
-                    let field_checks: Vec<_> = field_names
-                        .iter()
-                        .map(|field_name| {
-                            quote! { #field_name.is_safe() }
-                        })
+                        .map(|(i, _)| format_ident!("field{}", i + 1).into_token_stream())
                         .collect();
+                    let field_checks = bound_field_checks(&variant.fields, &field_names);
 
+                    // Skipped fields may not be used by any check.
                     quote! {
-                        #item_name::#variant_name(#(#field_names),*) => #(#field_checks)&&*
+                        #[allow(unused_variables)]
+                        #item_name::#variant_name(#(#field_names),*) => #field_checks
                     }
                 }
                 Fields::Unit => {
@@ -208,51 +523,37 @@ fn variant_checks(enum_data: DataEnum, item_name: &Ident) -> Vec<proc_macro2::To
                         #item_name::#variant_name => true
                     }
                 }
-                Fields::Named(_) => unreachable!("Enums do not have named fields"),
+                Fields::Named(fields) => {
+                    let field_names: Vec<_> =
+                        fields.named.iter().map(|field| field.ident.to_token_stream()).collect();
+                    let field_checks = bound_field_checks(&variant.fields, &field_names);
+
+                    quote! {
+                        #[allow(unused_variables)]
+                        #item_name::#variant_name { #(#field_names),* } => #field_checks
+                    }
+                }
             }
         })
         .collect()
 }
 
-/// Generate the body for the `is_safe` method.
-/// For each field of the type, enforce that it is safe.
-fn safe_body(fields: &Fields) -> proc_macro2::TokenStream {
-    match fields {
-        Fields::Named(ref fields) => {
-            let field_safe_calls: Vec<proc_macro2::TokenStream> = fields
-                .named
-                .iter()
-                .map(|field| {
-                    let name = &field.ident;
-                    quote_spanned! {field.span()=>
-                        self.#name.is_safe()
-                    }
-                })
-                .collect();
-            if !field_safe_calls.is_empty() {
-                quote! { #( #field_safe_calls )&&* }
-            } else {
-                quote! { true }
-            }
-        }
-        Fields::Unnamed(ref fields) => {
-            let field_safe_calls: Vec<proc_macro2::TokenStream> = fields
-                .unnamed
-                .iter()
-                .enumerate()
-                .map(|(idx, field)| {
-                    let field_idx = Index::from(idx);
-                    quote_spanned! {field.span()=>
-                        self.#field_idx.is_safe()
-                    }
-                })
-                .collect();
-            if !field_safe_calls.is_empty() {
-                quote! { #( #field_safe_calls )&&* }
-            } else {
-                quote! { true }
-            }
-        }
-        Fields::Unit => quote! { true },
-    }
+/// Generate the checks for the `is_safe` method of a struct.
+/// For each field of the type, enforce that it is safe, unless it is skipped, and that its
+/// additional predicates hold.
+fn safe_body(fields: &Fields) -> Vec<proc_macro2::TokenStream> {
+    fields
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, field)| {
+            let attrs = InvariantAttrs::parse(&field.attrs);
+            let name = match &field.ident {
+                Some(ident) => ident.to_token_stream(),
+                None => Index::from(idx).to_token_stream(),
+            };
+            let safe_call =
+                (!attrs.skip).then(|| quote_spanned! {field.span()=> self.#name.is_safe() });
+            safe_call.into_iter().chain(attrs.predicates.into_iter().map(|pred| quote! { (#pred) }))
+        })
+        .collect()
 }
@@ -0,0 +1,45 @@
+//! Parsing of the bounded quantifiers `forall!` and `exists!`.
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, ExprRange, Ident, RangeLimits, Token};
+
+/// A quantifier of the form `|var in start..end| pred`.
+pub(crate) struct Quantifier {
+    pub(crate) var: Ident,
+    pub(crate) start: Expr,
+    pub(crate) end: Expr,
+    pub(crate) pred: Expr,
+}
+
+impl Parse for Quantifier {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![|]>()?;
+        let var = input.parse()?;
+        input.parse::<Token![in]>()?;
+        // The range ends at the next `|`, so a bitwise or in the bounds must be parenthesized.
+        let range_tokens = input.step(|cursor| {
+            let mut rest = *cursor;
+            let mut tokens = TokenStream2::new();
+            while let Some((token, next)) = rest.token_tree() {
+                match &token {
+                    TokenTree::Punct(punct) if punct.as_char() == '|' => return Ok((tokens, rest)),
+                    _ => tokens.extend([token]),
+                }
+                rest = next;
+            }
+            Err(cursor.error("expected `|` after the range of the quantifier"))
+        })?;
+        input.parse::<Token![|]>()?;
+        let range: ExprRange = syn::parse2(range_tokens.clone())?;
+        let (Some(start), Some(end), RangeLimits::HalfOpen(_)) =
+            (range.start, range.end, range.limits)
+        else {
+            return Err(syn::Error::new_spanned(
+                range_tokens,
+                "quantifiers range over a bounded half-open range `start..end`",
+            ));
+        };
+        let pred = input.parse()?;
+        Ok(Quantifier { var, start: *start, end: *end, pred })
+    }
+}
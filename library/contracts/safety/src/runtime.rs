@@ -1,22 +1,263 @@
+//! Runtime implementation of the contract attributes.
+//!
+//! By default, the contract attributes are no-ops at runtime. When this crate is compiled with
+//! `--cfg contracts_runtime_checks`, contracts are instead turned into runtime checks that abort
+//! with a non-unwinding panic if they are violated, similar to `assert_unsafe_precondition!`.
+//! The checks are performed by `core::ub_checks::check_contract` and
+//! `core::ub_checks::check_contract_ensures`, which skip the check during const evaluation.
 use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_macro_input, parse_quote, Block, Expr, ExprCall, ExprReturn, ImplItemFn, Item,
+    ReturnType, Stmt, Type,
+};
 
-/// For now, runtime requires is a no-op.
+use crate::quantifiers::Quantifier;
+
+/// Whether contracts should be checked at runtime.
+const RUNTIME_CHECKS: bool = cfg!(contracts_runtime_checks);
+
+/// At runtime, the `requires` becomes a check at the beginning of the function body.
+pub(crate) fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !RUNTIME_CHECKS {
+        return item;
+    }
+    let cond = TokenStream2::from(attr);
+    let mut fn_item = parse_macro_input!(item with crate::traits::parse_contract_fn);
+    let msg = format!("{}: precondition violated: {}", fn_item.sig.ident, cond);
+    let cond = allow_unsafe_cond(&mut fn_item, cond);
+    let check: Stmt = parse_quote!(::core::ub_checks::check_contract(|| #cond, #msg););
+    fn_item.block.stmts.insert(0, check);
+    fn_item.into_token_stream().into()
+}
+
+/// At runtime, the `ensures` captures the value returned by the function body and checks the
+/// postcondition closure against it.
 ///
-/// TODO: At runtime the `requires` should become an assert unsafe precondition.
-pub(crate) fn requires(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    item
+/// Every `old(expr)` in the postcondition is replaced by a variable that holds the value of `expr`
+/// before the function body is executed, but after its preconditions are checked.
+/// In a `const fn`, these expressions must therefore be const-evaluable.
+///
+/// The `return` expressions of the body are checked individually. A body that uses `?` is
+/// instead wrapped in a closure, so that the early returns of `?` are checked as well. This is
+/// never needed in a `const fn`, where `?` is not available.
+pub(crate) fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !RUNTIME_CHECKS {
+        return item;
+    }
+    let msg_cond = TokenStream2::from(attr.clone());
+    let mut cond = parse_macro_input!(attr as Expr);
+    let mut fn_item = parse_macro_input!(item with crate::traits::parse_contract_fn);
+    if fn_item.sig.asyncness.is_some() {
+        // The value of an `async fn` is only available once the future completes.
+        return fn_item.into_token_stream().into();
+    }
+    let msg = format!("{}: postcondition violated: {}", fn_item.sig.ident, msg_cond);
+    let mut old_values = OldValues::default();
+    old_values.visit_expr_mut(&mut cond);
+    let (old_names, old_exprs): (Vec<_>, Vec<_>) = old_values.0.into_iter().unzip();
+    if let Expr::Closure(closure) = &mut cond {
+        let body = allow_unsafe_cond(&mut fn_item, closure.body.to_token_stream());
+        *closure.body = match closure.output {
+            ReturnType::Default => parse_quote!(#body),
+            ReturnType::Type(..) => parse_quote!({ #body }),
+        };
+    }
+
+    let mut stmts = std::mem::take(&mut fn_item.block.stmts);
+    let body_start = stmts.iter().take_while(|stmt| is_contract_check(stmt)).count();
+    let mut body =
+        Block { brace_token: fn_item.block.brace_token, stmts: stmts.split_off(body_start) };
+    // The return type is spelled out, since the postcondition closure alone does not always let
+    // the compiler infer it.
+    let ret_ty = match &fn_item.sig.output {
+        ReturnType::Default => quote!(()),
+        // The type of an `impl Trait` return value cannot be named, let the compiler infer it.
+        ReturnType::Type(_, ty) if contains_impl_trait(ty) => quote!(_),
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    };
+    let checked_body = if !TryFinder::find(&mut body) {
+        ReturnChecker { cond: &cond, msg: &msg, ret_ty: &ret_ty }.visit_block_mut(&mut body);
+        let ret = Ident::new("__ret", Span::mixed_site());
+        quote!(
+            let #ret: #ret_ty = #body;
+            // The body may always return early.
+            #[allow(unreachable_code)]
+            let #ret = ::core::ub_checks::check_contract_ensures::<#ret_ty, _>(#ret, #cond, #msg);
+            #ret
+        )
+    } else {
+        quote!(::core::ub_checks::check_contract_ensures::<#ret_ty, _>(
+            (|| -> #ret_ty #body)(),
+            #cond,
+            #msg
+        ))
+    };
+    fn_item.block = parse_quote!({
+        #(#stmts)*
+        #(let #old_names = #old_exprs;)*
+        #checked_body
+    });
+    fn_item.into_token_stream().into()
 }
 
-/// For now, runtime ensures is a no-op.
+/// At runtime, the write set of a function is not checked.
 ///
-/// TODO: At runtime the `ensures` should become an assert as well.
-pub(crate) fn ensures(_attr: TokenStream, item: TokenStream) -> TokenStream {
+/// Checking it would require a snapshot of all the memory that the function must not modify.
+pub(crate) fn modifies(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-/// For now, runtime loop_invariant is a no-op.
-///
-/// TODO: At runtime the `loop_invariant` should become an assert as well.
-pub(crate) fn loop_invariant(_attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
+/// At runtime, the `loop_invariant` is checked before every evaluation of the loop condition
+/// for `while` loops, and at the beginning of every iteration for other loops.
+pub(crate) fn loop_invariant(attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
+    if !RUNTIME_CHECKS {
+        return stmt_stream;
+    }
+    let inv = TokenStream2::from(attr);
+    let mut stmt = parse_macro_input!(stmt_stream as Stmt);
+    let msg = format!("loop invariant violated: {inv}");
+    let check: Stmt = parse_quote!(::core::ub_checks::check_contract(|| #inv, #msg););
+    let loop_expr = match &mut stmt {
+        Stmt::Expr(expr, _) => expr,
+        _ => return stmt.into_token_stream().into(),
+    };
+    match loop_expr {
+        Expr::While(while_expr) if !matches!(*while_expr.cond, Expr::Let(_)) => {
+            let cond = &while_expr.cond;
+            *while_expr.cond = parse_quote!({ #check #cond });
+        }
+        Expr::While(while_expr) => while_expr.body.stmts.insert(0, check),
+        Expr::Loop(loop_expr) => loop_expr.body.stmts.insert(0, check),
+        Expr::ForLoop(for_expr) => for_expr.body.stmts.insert(0, check),
+        _ => {}
+    }
+    stmt.into_token_stream().into()
+}
+
+/// At runtime, the write set of a loop is not checked, see [`modifies`].
+pub(crate) fn loop_modifies(_attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
     stmt_stream
 }
+
+/// At runtime, a quantifier is evaluated by iterating over its range.
+///
+/// Unlike the contracts, quantifiers are always expanded, since they may be used by `is_safe`.
+pub(crate) fn forall(input: TokenStream) -> TokenStream {
+    let Quantifier { var, start, end, pred } = parse_macro_input!(input as Quantifier);
+    quote!(::core::iter::Iterator::all(&mut (#start..#end), |#var| #pred)).into()
+}
+
+/// See [`forall`].
+pub(crate) fn exists(input: TokenStream) -> TokenStream {
+    let Quantifier { var, start, end, pred } = parse_macro_input!(input as Quantifier);
+    quote!(::core::iter::Iterator::any(&mut (#start..#end), |#var| #pred)).into()
+}
+
+/// Like the body of an `unsafe fn`, its contracts may use unsafe operations.
+fn allow_unsafe_cond(fn_item: &mut ImplItemFn, cond: TokenStream2) -> TokenStream2 {
+    if fn_item.sig.unsafety.is_none() {
+        return cond;
+    }
+    fn_item.attrs.push(parse_quote!(#[allow(unused_unsafe)]));
+    quote!(unsafe { #cond })
+}
+
+/// Whether `stmt` is a check inserted by [`requires`].
+fn is_contract_check(stmt: &Stmt) -> bool {
+    let Stmt::Expr(Expr::Call(call), Some(_)) = stmt else {
+        return false;
+    };
+    let Expr::Path(path) = &*call.func else {
+        return false;
+    };
+    let segments: Vec<_> = path.path.segments.iter().map(|segment| &segment.ident).collect();
+    segments == ["core", "ub_checks", "check_contract"]
+}
+
+/// Replace every `old(expr)` by a fresh variable, and collect the expressions to evaluate.
+#[derive(Default)]
+struct OldValues(Vec<(Ident, Expr)>);
+
+impl VisitMut for OldValues {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Call(call) if is_old(call) => {
+                let name = Ident::new(&format!("__old_{}", self.0.len()), Span::mixed_site());
+                let value = call.args.pop().unwrap().into_value();
+                self.0.push((name.clone(), value));
+                *expr = parse_quote!(#name);
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+}
+
+fn is_old(call: &ExprCall) -> bool {
+    matches!(&*call.func, Expr::Path(func) if func.path.is_ident("old")) && call.args.len() == 1
+}
+
+/// Check the postcondition on every `return` of a function body.
+struct ReturnChecker<'a> {
+    cond: &'a Expr,
+    msg: &'a str,
+    ret_ty: &'a TokenStream2,
+}
+
+impl VisitMut for ReturnChecker<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            // Returns inside closures and async blocks do not return from the function.
+            Expr::Closure(_) | Expr::Async(_) => {}
+            Expr::Return(ExprReturn { expr: ret, .. }) => {
+                let value = ret.take().map_or_else(|| quote!(()), |ret| ret.into_token_stream());
+                let (cond, msg, ret_ty) = (self.cond, self.msg, self.ret_ty);
+                *ret = Some(parse_quote!(
+                    ::core::ub_checks::check_contract_ensures::<#ret_ty, _>(#value, #cond, #msg)
+                ));
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut Item) {
+        // Nested items have their own return values.
+    }
+}
+
+/// Find the `?` operators that return from a function body.
+struct TryFinder(bool);
+
+impl TryFinder {
+    fn find(body: &mut Block) -> bool {
+        let mut finder = TryFinder(false);
+        finder.visit_block_mut(body);
+        finder.0
+    }
+}
+
+impl VisitMut for TryFinder {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            // `?` inside closures, async blocks and try blocks does not return from the function.
+            Expr::Closure(_) | Expr::Async(_) | Expr::TryBlock(_) => {}
+            Expr::Try(_) => self.0 = true,
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut Item) {}
+}
+
+fn contains_impl_trait(ty: &Type) -> bool {
+    fn visit(tokens: TokenStream2) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "impl",
+            TokenTree::Group(group) => visit(group.stream()),
+            _ => false,
+        })
+    }
+    visit(ty.to_token_stream())
+}
@@ -0,0 +1,234 @@
+//! Support for contracts on trait methods.
+//!
+//! An impl cannot see the attributes of the trait it implements, so `#[trait_contracts]` on a
+//! trait generates a companion `macro_rules!` macro that knows the contracts of every method.
+//! The impls annotated with `#[trait_contracts]` then pass each of their methods to that macro,
+//! which attaches the contracts of the corresponding trait method with `#[__trait_contract]`.
+use proc_macro::TokenStream;
+use proc_macro2::{Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
+use proc_macro_error::abort;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, Attribute, FnArg, ImplItem, ImplItemFn, Item, ItemImpl, ItemTrait, Pat,
+    Path, Token, TraitItem, TraitItemFn,
+};
+
+/// The name of the macro that holds the contracts of the methods of `trait_name`.
+fn contracts_macro(trait_name: &Ident) -> Ident {
+    format_ident!("__safety_contracts_{}", trait_name)
+}
+
+/// Whether `attr` is a `requires`, `ensures` or `modifies` contract.
+fn is_contract(attr: &Attribute) -> bool {
+    attr.path().segments.last().is_some_and(|segment| {
+        segment.ident == "requires" || segment.ident == "ensures" || segment.ident == "modifies"
+    })
+}
+
+/// The names of the arguments of a method, excluding the receiver.
+/// Arguments bound by a pattern other than an identifier are named `_`.
+fn arg_names<'a>(inputs: impl Iterator<Item = &'a FnArg>) -> Vec<Ident> {
+    inputs
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(pat) => Some(pat.ident.clone()),
+                _ => Some(Ident::new("_", Span::call_site())),
+            },
+        })
+        .collect()
+}
+
+/// Parse the function a contract is attached to, which may also be a method.
+///
+/// A required trait method has no body to check, so its contracts must be declared with
+/// `#[trait_contracts]` on the trait instead.
+pub(crate) fn parse_contract_fn(input: ParseStream) -> syn::Result<ImplItemFn> {
+    let fork = input.fork();
+    if let Ok(trait_fn) = fork.parse::<TraitItemFn>() {
+        if trait_fn.default.is_none() && fork.is_empty() {
+            return Err(syn::Error::new_spanned(
+                trait_fn.sig,
+                "contracts of a required trait method need `#[safety::trait_contracts]` on the trait",
+            ));
+        }
+    }
+    input.parse()
+}
+
+pub(crate) fn trait_contracts(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        // A single method of a trait impl, typically generated by a macro.
+        let trait_path = parse_macro_input!(attr as Path);
+        let impl_fn = parse_macro_input!(item as ImplItemFn);
+        return apply_fn_contracts(&trait_path, impl_fn).into();
+    }
+    match parse_macro_input!(item as Item) {
+        Item::Trait(item_trait) => define_contracts(item_trait),
+        Item::Impl(item_impl) => apply_contracts(item_impl),
+        item => abort!(item, "`trait_contracts` can only be applied to traits and trait impls"),
+    }
+}
+
+/// Remove the contracts from the required methods of the trait, and generate the macro that
+/// attaches them to the methods of the impls.
+///
+/// The contracts of provided methods are kept, so that the default implementation is checked as
+/// well.
+fn define_contracts(mut item_trait: ItemTrait) -> TokenStream {
+    let macro_name = contracts_macro(&item_trait.ident);
+    let mut arms = Vec::new();
+    for trait_fn in item_trait.items.iter_mut().filter_map(|item| match item {
+        TraitItem::Fn(trait_fn) => Some(trait_fn),
+        _ => None,
+    }) {
+        let contracts: Vec<_> = if trait_fn.default.is_some() {
+            trait_fn.attrs.iter().filter(|attr| is_contract(attr)).cloned().collect()
+        } else {
+            let (contracts, attrs) = trait_fn.attrs.drain(..).partition(is_contract);
+            trait_fn.attrs = attrs;
+            contracts
+        };
+        if contracts.is_empty() {
+            continue;
+        }
+        let contracts = contracts.iter().map(|attr| {
+            let kind = &attr.path().segments.last().unwrap().ident;
+            let args = attr.meta.require_list().map(|list| &list.tokens).unwrap_or_else(|err| {
+                abort!(err.span(), "invalid contract: {}", err)
+            });
+            quote!(#kind(#args))
+        });
+        let name = &trait_fn.sig.ident;
+        let params = arg_names(trait_fn.sig.inputs.iter());
+        arms.push(quote! {
+            (#name $($item:tt)*) => {
+                #[::safety::__trait_contract(params(#(#params),*), #(#contracts),*)]
+                $($item)*
+            };
+        });
+    }
+
+    quote! {
+        #item_trait
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            #(#arms)*
+            ($name:ident $($item:tt)*) => { $($item)* };
+        }
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name;
+    }
+    .into()
+}
+
+/// Pass every method of a trait impl to the contracts macro of the trait.
+fn apply_contracts(mut item_impl: ItemImpl) -> TokenStream {
+    let Some((_, trait_path, _)) = &item_impl.trait_ else {
+        abort!(item_impl.self_ty, "`trait_contracts` can only be applied to trait impls")
+    };
+    let trait_path = trait_path.clone();
+    for item in item_impl.items.iter_mut() {
+        if let ImplItem::Fn(impl_fn) = item {
+            *item = ImplItem::Verbatim(apply_fn_contracts(&trait_path, impl_fn.clone()));
+        }
+    }
+    item_impl.into_token_stream().into()
+}
+
+/// Pass a method of an impl of `trait_path` to the contracts macro of the trait.
+fn apply_fn_contracts(trait_path: &Path, impl_fn: ImplItemFn) -> TokenStream2 {
+    let mut macro_path = trait_path.clone();
+    let last = macro_path.segments.last_mut().unwrap();
+    last.ident = contracts_macro(&last.ident);
+    last.arguments = Default::default();
+    let name = &impl_fn.sig.ident;
+    quote!(#macro_path!(#name #impl_fn);)
+}
+
+/// The arguments of `__trait_contract`: the names of the arguments of the trait method,
+/// followed by its contracts.
+struct TraitContract {
+    params: Vec<Ident>,
+    contracts: Vec<(Ident, TokenStream2)>,
+}
+
+impl Parse for TraitContract {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut params = Vec::new();
+        let mut contracts = Vec::new();
+        for (kind, args) in Punctuated::<(Ident, Group), Token![,]>::parse_terminated_with(
+            input,
+            |input| Ok((input.parse()?, input.parse()?)),
+        )? {
+            if kind == "params" {
+                let parser = |input: ParseStream| {
+                    Punctuated::<Ident, Token![,]>::parse_terminated_with(input, Ident::parse_any)
+                };
+                params.extend(parser.parse2(args.stream())?);
+            } else {
+                contracts.push((kind, args.stream()));
+            }
+        }
+        Ok(TraitContract { params, contracts })
+    }
+}
+
+/// Attach the contracts of a trait method to a method of an impl.
+///
+/// The contracts come from the expansion of the contracts macro, so they are re-spanned to the
+/// method to be able to refer to its arguments. The arguments of the trait method are renamed to
+/// the arguments of the impl method.
+pub(crate) fn trait_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let TraitContract { params, contracts } = parse_macro_input!(attr as TraitContract);
+    let mut impl_fn = parse_macro_input!(item as ImplItemFn);
+    let span = impl_fn.sig.ident.span();
+    let renames: Vec<_> = params
+        .into_iter()
+        .zip(arg_names(impl_fn.sig.inputs.iter()))
+        .filter(|(_, impl_name)| impl_name != "_")
+        .collect();
+    let mut attrs: Vec<Attribute> = contracts
+        .into_iter()
+        .map(|(kind, args)| {
+            let args = respan(args, span, &renames);
+            syn::parse_quote!(#[::safety::#kind(#args)])
+        })
+        .collect();
+    attrs.append(&mut impl_fn.attrs);
+    impl_fn.attrs = attrs;
+    impl_fn.into_token_stream().into()
+}
+
+/// Resolve the tokens at `span`, and rename the identifiers according to `renames`.
+fn respan(tokens: TokenStream2, span: Span, renames: &[(Ident, Ident)]) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), respan(group.stream(), span, renames));
+                new_group.set_span(group.span().resolved_at(span));
+                TokenTree::Group(new_group)
+            }
+            TokenTree::Ident(ident) => {
+                let name = renames
+                    .iter()
+                    .find(|(trait_name, _)| *trait_name == ident)
+                    .map_or(&ident, |(_, impl_name)| impl_name);
+                let mut name = name.clone();
+                name.set_span(ident.span().resolved_at(span));
+                TokenTree::Ident(name)
+            }
+            mut token => {
+                token.set_span(token.span().resolved_at(span));
+                token
+            }
+        })
+        .collect()
+}
@@ -4,9 +4,9 @@
 // collections, resulting in having to optimize down excess IR multiple times.
 // Your performance intuition is useless. Run perf.
 
-use safety::{Invariant, ensures, requires};
+use safety::{Invariant, check_invariant, ensures, requires};
 
-#[cfg(kani)]
+#[cfg(any(kani, contracts_runtime_checks))]
 use crate::cmp;
 use crate::error::Error;
 use crate::intrinsics::{unchecked_add, unchecked_mul, unchecked_sub};
@@ -15,7 +15,7 @@ use crate::kani;
 use crate::mem::SizedTypeProperties;
 use crate::ptr::{Alignment, NonNull};
 // Used only for contract verification.
-#[allow(unused_imports)]
+#[cfg(any(kani, contracts_runtime_checks))]
 use crate::ub_checks::Invariant;
 use crate::{assert_unsafe_precondition, fmt, mem};
 
@@ -47,6 +47,7 @@ const fn size_align<T>() -> (usize, usize) {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[lang = "alloc_layout"]
 #[derive(Invariant)]
+#[safety_invariant(self.size <= Self::max_size_for_align(self.align))]
 pub struct Layout {
     // size of the requested block of memory, measured in bytes.
     size: usize,
@@ -61,6 +62,7 @@ pub struct Layout {
     align: Alignment,
 }
 
+#[check_invariant]
 impl Layout {
     /// Constructs a `Layout` from a given `size` and `align`,
     /// or returns `LayoutError` if any of the following conditions
@@ -649,6 +651,24 @@ mod verify {
         }
     }
 
+    // pub const fn size(&self) -> usize
+    #[kani::proof_for_contract(Layout::size)]
+    pub fn check_size_invariant() {
+        let layout = kani::any::<Layout>();
+        let _ = layout.size();
+    }
+
+    // The invariant of `Layout` is checked when its methods are called.
+    #[kani::proof]
+    #[kani::stub_verified(Layout::size)]
+    #[kani::should_panic]
+    pub fn check_size_broken_invariant() {
+        let align = kani::any::<Alignment>();
+        let size = kani::any_where(|s: &usize| *s > Layout::max_size_for_align(align));
+        let layout = Layout { size, align };
+        let _ = layout.size();
+    }
+
     // pub const fn align(&self) -> usize
     #[kani::proof]
     pub fn check_align() {
@@ -215,7 +215,7 @@ impl Invariant for &CStr {
 }
 
 // Helper function
-#[cfg(kani)]
+#[cfg(any(kani, contracts_runtime_checks))]
 #[requires(!ptr.is_null())]
 fn is_null_terminated(ptr: *const c_char) -> bool {
     let mut next = ptr;
@@ -50,13 +50,13 @@
 )]
 #![allow(missing_docs)]
 
-use safety::{ensures, requires};
+use safety::{ensures, modifies, requires};
 
 #[cfg(kani)]
 use crate::kani;
 use crate::marker::{ConstParamTy, DiscriminantKind, Tuple};
 use crate::ptr;
-#[cfg(kani)]
+#[cfg(any(kani, contracts_runtime_checks))]
 use crate::ub_checks;
 
 mod bounds;
@@ -3335,8 +3335,8 @@ pub const fn is_val_statically_known<T: Copy>(_arg: T) -> bool {
 #[inline]
 #[rustc_intrinsic]
 #[rustc_intrinsic_const_stable_indirect]
-#[cfg_attr(kani, kani::modifies(x))]
-#[cfg_attr(kani, kani::modifies(y))]
+#[modifies(x)]
+#[modifies(y)]
 #[requires(ub_checks::can_dereference(x) && ub_checks::can_write(x))]
 #[requires(ub_checks::can_dereference(y) && ub_checks::can_write(y))]
 #[requires(x.addr() != y.addr() || core::mem::size_of::<T>() == 0)]
@@ -3653,7 +3653,7 @@ pub const fn ptr_metadata<P: ptr::Pointee<Metadata = M> + ?Sized, M>(ptr: *const
 // Copy is "untyped".
 // TODO: we can no longer do this given https://github.com/model-checking/kani/issues/3325 (this
 // function used to have a dummy body, but no longer has)
-// #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+// #[modifies(crate::ptr::slice_from_raw_parts(dst, count))]
 // #[requires(!count.overflowing_mul(size_of::<T>()).1
 //   && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
 //   && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count))
@@ -3676,7 +3676,7 @@ pub const unsafe fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: us
 //   && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
 //   && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count)))]
 // #[ensures(|_| { check_copy_untyped(src, dst, count) })]
-// #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+// #[modifies(crate::ptr::slice_from_raw_parts(dst, count))]
 pub const unsafe fn copy<T>(src: *const T, dst: *mut T, count: usize);
 
 /// This is an accidentally-stable alias to [`ptr::write_bytes`]; use that instead.
@@ -3695,7 +3695,7 @@ pub const unsafe fn copy<T>(src: *const T, dst: *mut T, count: usize);
 // #[requires(ub_checks::maybe_is_aligned_and_not_null(dst as *const (), align_of::<T>(), T::IS_ZST || count == 0))]
 // #[ensures(|_|
 //     ub_checks::can_dereference(crate::ptr::slice_from_raw_parts(dst as *const u8, count * size_of::<T>())))]
-// #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+// #[modifies(crate::ptr::slice_from_raw_parts(dst, count))]
 pub const unsafe fn write_bytes<T>(dst: *mut T, val: u8, count: usize);
 
 /// Returns the minimum (IEEE 754-2008 minNum) of two `f16` values.
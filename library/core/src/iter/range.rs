@@ -6,6 +6,7 @@ use crate::mem;
 use crate::net::{Ipv4Addr, Ipv6Addr};
 use crate::num::NonZero;
 use crate::ops::{self, Try};
+use safety::{requires, trait_contracts};
 
 // Safety: All invariants are upheld.
 macro_rules! unsafe_impl_trusted_step {
@@ -21,6 +22,7 @@ unsafe_impl_trusted_step![AsciiChar char i8 i16 i32 i64 i128 isize u8 u16 u32 u6
 /// The *successor* operation moves towards values that compare greater.
 /// The *predecessor* operation moves towards values that compare lesser.
 #[unstable(feature = "step_trait", issue = "42168")]
+#[trait_contracts]
 pub trait Step: Clone + PartialOrd + Sized {
     /// Returns the bounds on the number of *successor* steps required to get from `start` to `end`
     /// like [`Iterator::size_hint()`][Iterator::size_hint()].
@@ -104,6 +106,7 @@ pub trait Step: Clone + PartialOrd + Sized {
     /// For any `a` and `n`, where no overflow occurs:
     ///
     /// * `Step::forward_unchecked(a, n)` is equivalent to `Step::forward(a, n)`
+    #[requires(Step::forward_checked(start.clone(), count).is_some())]
     unsafe fn forward_unchecked(start: Self, count: usize) -> Self {
         Step::forward(start, count)
     }
@@ -174,6 +177,7 @@ pub trait Step: Clone + PartialOrd + Sized {
     /// For any `a` and `n`, where no overflow occurs:
     ///
     /// * `Step::backward_unchecked(a, n)` is equivalent to `Step::backward(a, n)`
+    #[requires(Step::backward_checked(start.clone(), count).is_some())]
     unsafe fn backward_unchecked(start: Self, count: usize) -> Self {
         Step::backward(start, count)
     }
@@ -184,12 +188,14 @@ pub trait Step: Clone + PartialOrd + Sized {
 macro_rules! step_signed_methods {
     ($unsigned: ty) => {
         #[inline]
+        #[trait_contracts(Step)]
         unsafe fn forward_unchecked(start: Self, n: usize) -> Self {
             // SAFETY: the caller has to guarantee that `start + n` doesn't overflow.
             unsafe { start.checked_add_unsigned(n as $unsigned).unwrap_unchecked() }
         }
 
         #[inline]
+        #[trait_contracts(Step)]
         unsafe fn backward_unchecked(start: Self, n: usize) -> Self {
             // SAFETY: the caller has to guarantee that `start - n` doesn't overflow.
             unsafe { start.checked_sub_unsigned(n as $unsigned).unwrap_unchecked() }
@@ -200,12 +206,14 @@ macro_rules! step_signed_methods {
 macro_rules! step_unsigned_methods {
     () => {
         #[inline]
+        #[trait_contracts(Step)]
         unsafe fn forward_unchecked(start: Self, n: usize) -> Self {
             // SAFETY: the caller has to guarantee that `start + n` doesn't overflow.
             unsafe { start.unchecked_add(n as Self) }
         }
 
         #[inline]
+        #[trait_contracts(Step)]
         unsafe fn backward_unchecked(start: Self, n: usize) -> Self {
             // SAFETY: the caller has to guarantee that `start - n` doesn't overflow.
             unsafe { start.unchecked_sub(n as Self) }
@@ -441,6 +449,7 @@ step_integer_impls! {
 }
 
 #[unstable(feature = "step_trait", reason = "recently redesigned", issue = "42168")]
+#[trait_contracts]
 impl Step for char {
     #[inline]
     fn steps_between(&start: &char, &end: &char) -> (usize, Option<usize>) {
@@ -528,6 +537,7 @@ impl Step for char {
 }
 
 #[unstable(feature = "step_trait", reason = "recently redesigned", issue = "42168")]
+#[trait_contracts]
 impl Step for AsciiChar {
     #[inline]
     fn steps_between(&start: &AsciiChar, &end: &AsciiChar) -> (usize, Option<usize>) {
@@ -570,6 +580,7 @@ impl Step for AsciiChar {
 }
 
 #[unstable(feature = "step_trait", reason = "recently redesigned", issue = "42168")]
+#[trait_contracts]
 impl Step for Ipv4Addr {
     #[inline]
     fn steps_between(&start: &Ipv4Addr, &end: &Ipv4Addr) -> (usize, Option<usize>) {
@@ -602,6 +613,7 @@ impl Step for Ipv4Addr {
 }
 
 #[unstable(feature = "step_trait", reason = "recently redesigned", issue = "42168")]
+#[trait_contracts]
 impl Step for Ipv6Addr {
     #[inline]
     fn steps_between(&start: &Ipv6Addr, &end: &Ipv6Addr) -> (usize, Option<usize>) {
@@ -1395,3 +1407,34 @@ unsafe impl<A: TrustedStep> TrustedLen for ops::RangeInclusive<A> {}
 
 #[stable(feature = "fused", since = "1.26.0")]
 impl<A: Step> FusedIterator for ops::RangeInclusive<A> {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    // unsafe fn forward_unchecked(start: Self, count: usize) -> Self
+    #[kani::proof_for_contract(<u8 as Step>::forward_unchecked)]
+    fn check_forward_unchecked_u8() {
+        let start = kani::any::<u8>();
+        let count = kani::any::<usize>();
+        let _ = unsafe { Step::forward_unchecked(start, count) };
+    }
+
+    // unsafe fn backward_unchecked(start: Self, count: usize) -> Self
+    #[kani::proof_for_contract(<i8 as Step>::backward_unchecked)]
+    fn check_backward_unchecked_i8() {
+        let start = kani::any::<i8>();
+        let count = kani::any::<usize>();
+        let _ = unsafe { Step::backward_unchecked(start, count) };
+    }
+
+    // unsafe fn forward_unchecked(start: char, count: usize) -> char
+    #[kani::proof_for_contract(<char as Step>::forward_unchecked)]
+    fn check_forward_unchecked_char() {
+        let start = kani::any::<char>();
+        let count = kani::any::<usize>();
+        let _ = unsafe { Step::forward_unchecked(start, count) };
+    }
+}
@@ -1,5 +1,11 @@
+use safety::{ensures, requires};
+
 use crate::any::type_name;
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::ManuallyDrop;
+#[cfg(any(kani, contracts_runtime_checks))]
+use crate::ub_checks;
 use crate::{fmt, intrinsics, ptr, slice};
 
 /// A wrapper type to construct uninitialized instances of `T`.
@@ -377,6 +383,10 @@ impl<T> MaybeUninit<T> {
     #[rustc_diagnostic_item = "maybe_uninit_zeroed"]
     #[stable(feature = "maybe_uninit", since = "1.36.0")]
     #[rustc_const_stable(feature = "const_maybe_uninit_zeroed", since = "1.75.0")]
+    #[ensures(|result: &MaybeUninit<T>| result.as_bytes().iter().all(|byte| {
+        // SAFETY: every byte of `result`, padding included, has just been zeroed.
+        unsafe { byte.assume_init() == 0 }
+    }))]
     pub const fn zeroed() -> MaybeUninit<T> {
         let mut u = MaybeUninit::<T>::uninit();
         // SAFETY: `u.as_mut_ptr()` points to allocated memory.
@@ -611,6 +621,7 @@ impl<T> MaybeUninit<T> {
     #[inline(always)]
     #[rustc_diagnostic_item = "assume_init"]
     #[track_caller]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init(self) -> T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -681,6 +692,7 @@ impl<T> MaybeUninit<T> {
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init_read", since = "1.75.0")]
     #[inline(always)]
     #[track_caller]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init_read(&self) -> T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // Reading from `self.as_ptr()` is safe since `self` should be initialized.
@@ -712,6 +724,7 @@ impl<T> MaybeUninit<T> {
     ///
     /// [`assume_init`]: MaybeUninit::assume_init
     #[stable(feature = "maybe_uninit_extra", since = "1.60.0")]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub unsafe fn assume_init_drop(&mut self) {
         // SAFETY: the caller must guarantee that `self` is initialized and
         // satisfies all invariants of `T`.
@@ -778,6 +791,8 @@ impl<T> MaybeUninit<T> {
     #[stable(feature = "maybe_uninit_ref", since = "1.55.0")]
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init_ref", since = "1.59.0")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
+    #[ensures(|result: &&T| ptr::eq(*result, self.as_ptr()))]
     pub const unsafe fn assume_init_ref(&self) -> &T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -895,6 +910,8 @@ impl<T> MaybeUninit<T> {
     #[stable(feature = "maybe_uninit_ref", since = "1.55.0")]
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init", since = "1.84.0")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
+    #[ensures(|result: &&mut T| ptr::eq(&**result, old(self.as_ptr())))]
     pub const unsafe fn assume_init_mut(&mut self) -> &mut T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -932,6 +949,7 @@ impl<T> MaybeUninit<T> {
     #[unstable(feature = "maybe_uninit_array_assume_init", issue = "96097")]
     #[inline(always)]
     #[track_caller]
+    #[requires(ub_checks::can_dereference(array.as_ptr().cast::<[T; N]>()))]
     pub const unsafe fn array_assume_init<const N: usize>(array: [Self; N]) -> [T; N] {
         // SAFETY:
         // * The caller guarantees that all elements of the array are initialized
@@ -962,6 +980,9 @@ impl<T> MaybeUninit<T> {
     /// assert_eq!(bytes, val.to_ne_bytes());
     /// ```
     #[unstable(feature = "maybe_uninit_as_bytes", issue = "93092")]
+    #[ensures(|result: &&[MaybeUninit<u8>]| {
+        result.len() == super::size_of::<T>() && ptr::eq(result.as_ptr().cast(), self.as_ptr())
+    })]
     pub const fn as_bytes(&self) -> &[MaybeUninit<u8>] {
         // SAFETY: MaybeUninit<u8> is always valid, even for padding bytes
         unsafe {
@@ -1009,6 +1030,7 @@ impl<T> MaybeUninit<T> {
         note = "replaced by inherent assume_init_ref method; will eventually be removed",
         since = "1.83.0"
     )]
+    #[requires(ub_checks::can_dereference(slice as *const [Self] as *const [T]))]
     pub const unsafe fn slice_assume_init_ref(slice: &[Self]) -> &[T] {
         // SAFETY: Same for both methods.
         unsafe { slice.assume_init_ref() }
@@ -1020,6 +1042,7 @@ impl<T> MaybeUninit<T> {
         note = "replaced by inherent assume_init_mut method; will eventually be removed",
         since = "1.83.0"
     )]
+    #[requires(ub_checks::can_dereference(slice as *const [Self] as *const [T]))]
     pub const unsafe fn slice_assume_init_mut(slice: &mut [Self]) -> &mut [T] {
         // SAFETY: Same for both methods.
         unsafe { slice.assume_init_mut() }
@@ -1173,6 +1196,9 @@ impl<T> [MaybeUninit<T>] {
     ///
     /// [`write_clone_of_slice`]: slice::write_clone_of_slice
     #[unstable(feature = "maybe_uninit_write_slice", issue = "79995")]
+    #[ensures(|result: &&mut [T]| {
+        result.len() == src.len() && ptr::eq(result.as_ptr(), old(self.as_ptr().cast::<T>()))
+    })]
     pub const fn write_copy_of_slice(&mut self, src: &[T]) -> &mut [T]
     where
         T: Copy,
@@ -1236,6 +1262,9 @@ impl<T> [MaybeUninit<T>] {
     ///
     /// [`write_copy_of_slice`]: slice::write_copy_of_slice
     #[unstable(feature = "maybe_uninit_write_slice", issue = "79995")]
+    #[ensures(|result: &&mut [T]| {
+        result.len() == src.len() && ptr::eq(result.as_ptr(), old(self.as_ptr().cast::<T>()))
+    })]
     pub fn write_clone_of_slice(&mut self, src: &[T]) -> &mut [T]
     where
         T: Clone,
@@ -1441,6 +1470,9 @@ impl<T> [MaybeUninit<T>] {
     /// assert_eq!(&[val1, val2], &[0x1234u16, 0x5678u16]);
     /// ```
     #[unstable(feature = "maybe_uninit_as_bytes", issue = "93092")]
+    #[ensures(|result: &&[MaybeUninit<u8>]| {
+        result.len() == super::size_of_val(self) && ptr::eq(result.as_ptr().cast(), self.as_ptr())
+    })]
     pub const fn as_bytes(&self) -> &[MaybeUninit<u8>] {
         // SAFETY: MaybeUninit<u8> is always valid, even for padding bytes
         unsafe {
@@ -1499,6 +1531,7 @@ impl<T> [MaybeUninit<T>] {
     /// behaviour.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self as *const Self as *const [T]))]
     pub unsafe fn assume_init_drop(&mut self) {
         if !self.is_empty() {
             // SAFETY: the caller must guarantee that every element of `self`
@@ -1517,6 +1550,8 @@ impl<T> [MaybeUninit<T>] {
     /// the slice really is in an initialized state.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self as *const Self as *const [T]))]
+    #[ensures(|result: &&[T]| ptr::eq(*result, self as *const Self as *const [T]))]
     pub const unsafe fn assume_init_ref(&self) -> &[T] {
         // SAFETY: casting `slice` to a `*const [T]` is safe since the caller guarantees that
         // `slice` is initialized, and `MaybeUninit` is guaranteed to have the same layout as `T`.
@@ -1535,6 +1570,8 @@ impl<T> [MaybeUninit<T>] {
     /// be used to initialize a `MaybeUninit` slice.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self as *const Self as *const [T]))]
+    #[ensures(|result: &&mut [T]| ptr::eq(&**result, old(self as *const Self as *const [T])))]
     pub const unsafe fn assume_init_mut(&mut self) -> &mut [T] {
         // SAFETY: similar to safety notes for `slice_get_ref`, but we have a
         // mutable reference which is also guaranteed to be valid for writes.
@@ -1621,3 +1658,240 @@ impl<T: Copy> SpecFill<T> for [MaybeUninit<T>] {
         self.fill(MaybeUninit::new(value));
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::num::NonZero;
+
+    const LEN: usize = 3;
+
+    /// A reference to one of a few static values, since references are not `kani::Arbitrary`.
+    fn any_ref() -> &'static u32 {
+        static VALUES: [u32; LEN] = [0, 1, u32::MAX];
+        &VALUES[kani::any_where(|&idx: &usize| idx < LEN)]
+    }
+
+    macro_rules! generate_maybe_uninit_harnesses {
+        ($ty:ty, $any:expr, $mod_name:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                // pub const fn zeroed() -> MaybeUninit<T>
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::zeroed)]
+                pub fn check_zeroed() {
+                    let _ = MaybeUninit::<$ty>::zeroed();
+                }
+
+                // pub const unsafe fn assume_init(self) -> T
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init)]
+                pub fn check_assume_init() {
+                    let value: $ty = $any;
+                    let uninit = MaybeUninit::new(value);
+                    assert!(unsafe { uninit.assume_init() } == value);
+                }
+
+                // pub const unsafe fn assume_init_read(&self) -> T
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_read)]
+                pub fn check_assume_init_read() {
+                    let value: $ty = $any;
+                    let uninit = MaybeUninit::new(value);
+                    assert!(unsafe { uninit.assume_init_read() } == value);
+                }
+
+                // pub unsafe fn assume_init_drop(&mut self)
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_drop)]
+                pub fn check_assume_init_drop() {
+                    let mut uninit = MaybeUninit::<$ty>::new($any);
+                    unsafe { uninit.assume_init_drop() };
+                }
+
+                // pub const unsafe fn assume_init_ref(&self) -> &T
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_ref)]
+                pub fn check_assume_init_ref() {
+                    let value: $ty = $any;
+                    let uninit = MaybeUninit::new(value);
+                    assert!(*unsafe { uninit.assume_init_ref() } == value);
+                }
+
+                // pub const unsafe fn assume_init_mut(&mut self) -> &mut T
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_mut)]
+                pub fn check_assume_init_mut() {
+                    let value: $ty = $any;
+                    let mut uninit = MaybeUninit::<$ty>::uninit();
+                    uninit.write(value);
+                    let init = unsafe { uninit.assume_init_mut() };
+                    assert!(*init == value);
+                    *init = $any;
+                }
+
+                // pub const unsafe fn array_assume_init<const N: usize>(array: [Self; N]) -> [T; N]
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::array_assume_init)]
+                pub fn check_array_assume_init() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let array = values.map(MaybeUninit::new);
+                    assert!(unsafe { MaybeUninit::array_assume_init(array) } == values);
+                }
+
+                // pub const fn as_bytes(&self) -> &[MaybeUninit<u8>]
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::as_bytes)]
+                pub fn check_as_bytes() {
+                    let uninit = if kani::any() {
+                        MaybeUninit::<$ty>::new($any)
+                    } else {
+                        MaybeUninit::uninit()
+                    };
+                    let _ = uninit.as_bytes();
+                }
+
+                // pub const unsafe fn slice_assume_init_ref(slice: &[Self]) -> &[T]
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::slice_assume_init_ref)]
+                #[allow(deprecated)]
+                pub fn check_slice_assume_init_ref() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let array = values.map(MaybeUninit::new);
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    let slice = unsafe { MaybeUninit::slice_assume_init_ref(&array[..len]) };
+                    assert!(slice == &values[..len]);
+                }
+
+                // pub const unsafe fn slice_assume_init_mut(slice: &mut [Self]) -> &mut [T]
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::slice_assume_init_mut)]
+                #[allow(deprecated)]
+                pub fn check_slice_assume_init_mut() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let mut array = values.map(MaybeUninit::new);
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    let slice = unsafe { MaybeUninit::slice_assume_init_mut(&mut array[..len]) };
+                    assert!(slice == &values[..len]);
+                }
+
+                // pub unsafe fn assume_init_drop(&mut self)
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::assume_init_drop)]
+                pub fn check_slice_assume_init_drop() {
+                    let mut array = [$any, $any, $any].map(MaybeUninit::<$ty>::new);
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    unsafe { array[..len].assume_init_drop() };
+                }
+
+                // pub const unsafe fn assume_init_ref(&self) -> &[T]
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::assume_init_ref)]
+                pub fn check_slice_assume_init_ref() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let array = values.map(MaybeUninit::new);
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    assert!(unsafe { array[..len].assume_init_ref() } == &values[..len]);
+                }
+
+                // pub const unsafe fn assume_init_mut(&mut self) -> &mut [T]
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::assume_init_mut)]
+                pub fn check_slice_assume_init_mut() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let mut array = values.map(MaybeUninit::new);
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    assert!(unsafe { array[..len].assume_init_mut() } == &values[..len]);
+                }
+
+                // pub const fn write_copy_of_slice(&mut self, src: &[T]) -> &mut [T]
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::write_copy_of_slice)]
+                pub fn check_write_copy_of_slice() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let mut array = [MaybeUninit::<$ty>::uninit(); LEN];
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    let init = array[..len].write_copy_of_slice(&values[..len]);
+                    assert!(init == &values[..len]);
+                }
+
+                // pub fn write_clone_of_slice(&mut self, src: &[T]) -> &mut [T]
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::write_clone_of_slice)]
+                pub fn check_write_clone_of_slice() {
+                    let values: [$ty; LEN] = [$any, $any, $any];
+                    let mut array = [MaybeUninit::<$ty>::uninit(); LEN];
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    let init = array[..len].write_clone_of_slice(&values[..len]);
+                    assert!(init == &values[..len]);
+                }
+
+                // pub const fn as_bytes(&self) -> &[MaybeUninit<u8>]
+                #[kani::proof_for_contract(<[MaybeUninit<$ty>]>::as_bytes)]
+                pub fn check_slice_as_bytes() {
+                    let array = [MaybeUninit::<$ty>::uninit(); LEN];
+                    let len = kani::any_where(|&len: &usize| len <= LEN);
+                    let _ = array[..len].as_bytes();
+                }
+            }
+        };
+    }
+
+    generate_maybe_uninit_harnesses!(u8, kani::any(), check_u8);
+    generate_maybe_uninit_harnesses!(u32, kani::any(), check_u32);
+    generate_maybe_uninit_harnesses!(i64, kani::any(), check_i64);
+    generate_maybe_uninit_harnesses!((u8, u32), kani::any(), check_tuple);
+    generate_maybe_uninit_harnesses!(bool, kani::any(), check_bool);
+    generate_maybe_uninit_harnesses!(char, kani::any(), check_char);
+    generate_maybe_uninit_harnesses!(&'static u32, any_ref(), check_ref);
+    generate_maybe_uninit_harnesses!(NonZero<u32>, kani::any(), check_nonzero);
+    generate_maybe_uninit_harnesses!(Option<NonZero<u32>>, kani::any(), check_option_nonzero);
+
+    // The following harnesses check that the preconditions reject the classic misuses, where
+    // the bytes behind a `MaybeUninit<T>` are not a valid `T`.
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<bool>::assume_init)]
+    #[kani::should_panic]
+    fn should_fail_assume_init_uninit_bool() {
+        let uninit = MaybeUninit::<bool>::uninit();
+        let _ = unsafe { uninit.assume_init() };
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<bool>::assume_init)]
+    #[kani::should_panic]
+    fn should_fail_assume_init_invalid_bool() {
+        let byte: u8 = kani::any_where(|&byte: &u8| byte > 1);
+        let uninit: MaybeUninit<bool> = unsafe { crate::mem::transmute(byte) };
+        let _ = unsafe { uninit.assume_init() };
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<bool>::assume_init)]
+    fn should_succeed_assume_init_valid_bool() {
+        let byte: u8 = kani::any_where(|&byte: &u8| byte <= 1);
+        let uninit: MaybeUninit<bool> = unsafe { crate::mem::transmute(byte) };
+        assert_eq!(unsafe { uninit.assume_init() }, byte == 1);
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<char>::assume_init_read)]
+    #[kani::should_panic]
+    fn should_fail_assume_init_read_invalid_char() {
+        let code: u32 = kani::any_where(|&code: &u32| char::from_u32(code).is_none());
+        let uninit: MaybeUninit<char> = unsafe { crate::mem::transmute(code) };
+        let _ = unsafe { uninit.assume_init_read() };
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<NonZero<u32>>::assume_init)]
+    #[kani::should_panic]
+    fn should_fail_assume_init_zeroed_nonzero() {
+        let _ = unsafe { MaybeUninit::<NonZero<u32>>::zeroed().assume_init() };
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(MaybeUninit::<&'static u32>::assume_init_ref)]
+    #[kani::should_panic]
+    fn should_fail_assume_init_ref_zeroed_ref() {
+        let uninit = MaybeUninit::<&'static u32>::zeroed();
+        let _ = unsafe { uninit.assume_init_ref() };
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(<[MaybeUninit<bool>]>::assume_init_ref)]
+    #[kani::should_panic]
+    fn should_fail_slice_assume_init_ref_partly_uninit_bool() {
+        let mut array = [MaybeUninit::<bool>::uninit(); LEN];
+        array[0].write(kani::any());
+        let _ = unsafe { array.assume_init_ref() };
+    }
+}
@@ -5,9 +5,9 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use safety::modifies;
+
 use crate::alloc::Layout;
-#[cfg(kani)]
-use crate::kani;
 use crate::marker::DiscriminantKind;
 use crate::{clone, cmp, fmt, hash, intrinsics, ptr};
 
@@ -721,8 +721,8 @@ pub unsafe fn uninitialized<T>() -> T {
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_const_stable(feature = "const_swap", since = "1.85.0")]
 #[rustc_diagnostic_item = "mem_swap"]
-#[cfg_attr(kani, crate::kani::modifies(x))]
-#[cfg_attr(kani, crate::kani::modifies(y))]
+#[modifies(x)]
+#[modifies(y)]
 pub const fn swap<T>(x: &mut T, y: &mut T) {
     // SAFETY: `&mut` guarantees these are typed readable and writable
     // as well as non-overlapping.
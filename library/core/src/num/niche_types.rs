@@ -4,6 +4,8 @@
     reason = "for core, alloc, and std internals until pattern types are further along"
 )]
 
+use safety::check_invariant;
+
 use crate::cmp::Ordering;
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
@@ -112,6 +114,7 @@ impl Nanoseconds {
     pub const ZERO: Self = unsafe { Nanoseconds::new_unchecked(0) };
 }
 
+#[check_invariant]
 impl Default for Nanoseconds {
     #[inline]
     fn default() -> Self {
@@ -1,4 +1,4 @@
-use safety::{ensures, invariant, requires};
+use safety::{check_invariant, ensures, invariant, requires};
 
 #[cfg(kani)]
 use crate::kani;
@@ -29,6 +29,7 @@ fn _alignment_can_be_structurally_matched(a: Alignment) -> bool {
     matches!(a, Alignment::MIN)
 }
 
+#[check_invariant]
 impl Alignment {
     /// The smallest possible alignment, 1.
     ///
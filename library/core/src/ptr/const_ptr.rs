@@ -489,7 +489,6 @@ impl<T: ?Sized> *const T {
     #[requires(
         count == 0 ||
         (
-            (core::mem::size_of_val_raw(self) > 0) &&
             (self.addr() as isize).checked_add(count).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_offset(count))
         )
@@ -1019,7 +1018,6 @@ impl<T: ?Sized> *const T {
         // allocation
         (
             (count <= isize::MAX as usize) &&
-            (core::mem::size_of_val_raw(self) > 0) &&
             ((self.addr() as isize).checked_add(count as isize).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_add(count)))
         )
@@ -1162,7 +1160,6 @@ impl<T: ?Sized> *const T {
         // same allocation.
         (
             (count <= isize::MAX as usize) &&
-            (core::mem::size_of_val_raw(self) > 0) &&
             ((self.addr() as isize).checked_sub(count as isize).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_sub(count)))
         )
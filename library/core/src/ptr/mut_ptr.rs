@@ -491,7 +491,6 @@ impl<T: ?Sized> *mut T {
     #[requires(
         count == 0 ||
         (
-            (core::mem::size_of_val_raw(self) > 0) &&
             (self.addr() as isize).checked_add(count).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_offset(count))
         )
@@ -1115,7 +1114,6 @@ impl<T: ?Sized> *mut T {
         // allocation
         (
             (count <= isize::MAX as usize) &&
-            (core::mem::size_of_val_raw(self) > 0) &&
             ((self.addr() as isize).checked_add(count as isize).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_add(count)))
         )
@@ -1260,7 +1258,6 @@ impl<T: ?Sized> *mut T {
         // same allocation.
         (
             (count <= isize::MAX as usize) &&
-            (core::mem::size_of_val_raw(self) > 0) &&
             ((self.addr() as isize).checked_sub(count as isize).is_some()) &&
             (core::ub_checks::same_allocation(self, self.wrapping_byte_sub(count)))
         )
@@ -42,6 +42,7 @@ impl [u8] {
     #[unstable(feature = "ascii_char", issue = "110998")]
     #[must_use]
     #[inline]
+    #[safety::requires(safety::forall!(|i in 0..self.len()| self[i].is_ascii()))]
     pub const unsafe fn as_ascii_unchecked(&self) -> &[ascii::Char] {
         let byte_ptr: *const [u8] = self;
         let ascii_ptr = byte_ptr as *const [ascii::Char];
@@ -515,6 +516,16 @@ const fn is_ascii(bytes: &[u8]) -> bool {
 pub mod verify {
     use super::*;
 
+    // pub const unsafe fn as_ascii_unchecked(&self) -> &[ascii::Char]
+    #[kani::proof_for_contract(<[u8]>::as_ascii_unchecked)]
+    #[kani::unwind(9)]
+    pub fn check_as_ascii_unchecked() {
+        let bytes = kani::any_where(|bytes: &[u8; 8]| bytes.iter().all(u8::is_ascii));
+        let slice = kani::slice::any_slice_of_array(&bytes);
+        let chars = unsafe { slice.as_ascii_unchecked() };
+        assert_eq!(chars.len(), slice.len());
+    }
+
     #[kani::proof]
     #[kani::unwind(8)]
     // FIXME: the loop invariant in the x_64 & sse2 version of is_ascii
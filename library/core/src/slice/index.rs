@@ -1,10 +1,12 @@
 //! Indexing implementations for `[T]`.
 
+use safety::trait_contracts;
+
 #[cfg(not(bootstrap))]
 use crate::intrinsics::slice_get_unchecked;
 use crate::panic::const_panic;
 use crate::ub_checks::assert_unsafe_precondition;
-use crate::{ops, range};
+use crate::{mem, ops, range};
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T, I> ops::Index<I> for [T]
@@ -125,6 +127,20 @@ const unsafe fn get_offset_len_mut_noubcheck<T>(
     crate::intrinsics::aggregate_raw_ptr(ptr, len)
 }
 
+/// Whether the memory `part` points to lies within the memory `whole` points to. Only used in
+/// contracts.
+///
+/// # Safety
+///
+/// Both pointers must satisfy the requirements of [`mem::size_of_val_raw`].
+#[allow(dead_code)]
+unsafe fn is_within<T: ?Sized, U: ?Sized>(part: *const T, whole: *const U) -> bool {
+    // SAFETY: The caller guarantees that both sizes can be computed.
+    let (part_size, whole_size) =
+        unsafe { (mem::size_of_val_raw(part), mem::size_of_val_raw(whole)) };
+    part.addr() >= whole.addr() && part.addr() - whole.addr() + part_size <= whole_size
+}
+
 mod private_slice_index {
     use super::{ops, range};
 
@@ -175,6 +191,7 @@ mod private_slice_index {
     message = "the type `{T}` cannot be indexed by `{Self}`",
     label = "slice indices are of type `usize` or ranges of `usize`"
 )]
+#[trait_contracts]
 pub unsafe trait SliceIndex<T: ?Sized>: private_slice_index::Sealed {
     /// The output type returned by methods.
     #[stable(feature = "slice_get_slice", since = "1.28.0")]
@@ -198,6 +215,7 @@ pub unsafe trait SliceIndex<T: ?Sized>: private_slice_index::Sealed {
     ///
     /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
     #[unstable(feature = "slice_index_methods", issue = "none")]
+    #[safety::ensures(|result: &*const Self::Output| unsafe { is_within(*result, slice) })]
     unsafe fn get_unchecked(self, slice: *const T) -> *const Self::Output;
 
     /// Returns a mutable pointer to the output at this location, without
@@ -208,6 +226,7 @@ pub unsafe trait SliceIndex<T: ?Sized>: private_slice_index::Sealed {
     ///
     /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
     #[unstable(feature = "slice_index_methods", issue = "none")]
+    #[safety::ensures(|result: &*mut Self::Output| unsafe { is_within(*result, slice) })]
     unsafe fn get_unchecked_mut(self, slice: *mut T) -> *mut Self::Output;
 
     /// Returns a shared reference to the output at this location, panicking
@@ -225,6 +244,7 @@ pub unsafe trait SliceIndex<T: ?Sized>: private_slice_index::Sealed {
 
 /// The methods `index` and `index_mut` panic if the index is out of bounds.
 #[stable(feature = "slice_get_slice_impls", since = "1.15.0")]
+#[trait_contracts]
 unsafe impl<T> SliceIndex<[T]> for usize {
     type Output = T;
 
@@ -266,6 +286,7 @@ unsafe impl<T> SliceIndex<[T]> for usize {
 
     #[inline]
     #[track_caller]
+    #[safety::requires(self < slice.len())]
     unsafe fn get_unchecked(self, slice: *const [T]) -> *const T {
         assert_unsafe_precondition!(
             check_language_ub,
@@ -293,6 +314,7 @@ unsafe impl<T> SliceIndex<[T]> for usize {
 
     #[inline]
     #[track_caller]
+    #[safety::requires(self < slice.len())]
     unsafe fn get_unchecked_mut(self, slice: *mut [T]) -> *mut T {
         assert_unsafe_precondition!(
             check_library_ub,
@@ -403,6 +425,7 @@ unsafe impl<T> SliceIndex<[T]> for ops::IndexRange {
 /// - the start of the range is greater than the end of the range or
 /// - the end of the range is out of bounds.
 #[stable(feature = "slice_get_slice_impls", since = "1.15.0")]
+#[trait_contracts]
 unsafe impl<T> SliceIndex<[T]> for ops::Range<usize> {
     type Output = [T];
 
@@ -433,6 +456,7 @@ unsafe impl<T> SliceIndex<[T]> for ops::Range<usize> {
 
     #[inline]
     #[track_caller]
+    #[safety::requires(self.start <= self.end && self.end <= slice.len())]
     unsafe fn get_unchecked(self, slice: *const [T]) -> *const [T] {
         assert_unsafe_precondition!(
             check_library_ub,
@@ -458,6 +482,7 @@ unsafe impl<T> SliceIndex<[T]> for ops::Range<usize> {
 
     #[inline]
     #[track_caller]
+    #[safety::requires(self.start <= self.end && self.end <= slice.len())]
     unsafe fn get_unchecked_mut(self, slice: *mut [T]) -> *mut [T] {
         assert_unsafe_precondition!(
             check_library_ub,
@@ -1075,3 +1100,44 @@ unsafe impl<T> SliceIndex<[T]> for (ops::Bound<usize>, ops::Bound<usize>) {
         into_slice_range(slice.len(), self).index_mut(slice)
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    // unsafe fn get_unchecked(self, slice: *const [T]) -> *const T
+    #[kani::proof_for_contract(<usize as SliceIndex<[u32]>>::get_unchecked)]
+    fn check_get_unchecked_usize() {
+        let array: [u32; 8] = kani::any();
+        let index = kani::any_where(|i: &usize| *i < array.len());
+        let _ = unsafe { SliceIndex::get_unchecked(index, &array[..] as *const [u32]) };
+    }
+
+    // unsafe fn get_unchecked_mut(self, slice: *mut [T]) -> *mut T
+    #[kani::proof_for_contract(<usize as SliceIndex<[u32]>>::get_unchecked_mut)]
+    fn check_get_unchecked_mut_usize() {
+        let mut array: [u32; 8] = kani::any();
+        let index = kani::any_where(|i: &usize| *i < array.len());
+        let _ = unsafe { SliceIndex::get_unchecked_mut(index, &mut array[..] as *mut [u32]) };
+    }
+
+    // unsafe fn get_unchecked(self, slice: *const [T]) -> *const [T]
+    #[kani::proof_for_contract(<ops::Range<usize> as SliceIndex<[u32]>>::get_unchecked)]
+    fn check_get_unchecked_range() {
+        let array: [u32; 8] = kani::any();
+        let end = kani::any_where(|end: &usize| *end <= array.len());
+        let start = kani::any_where(|start: &usize| *start <= end);
+        let _ = unsafe { SliceIndex::get_unchecked(start..end, &array[..] as *const [u32]) };
+    }
+
+    // unsafe fn get_unchecked_mut(self, slice: *mut [T]) -> *mut [T]
+    #[kani::proof_for_contract(<ops::Range<usize> as SliceIndex<[u32]>>::get_unchecked_mut)]
+    fn check_get_unchecked_mut_range() {
+        let mut array: [u32; 8] = kani::any();
+        let end = kani::any_where(|end: &usize| *end <= array.len());
+        let start = kani::any_where(|start: &usize| *start <= end);
+        let _ = unsafe { SliceIndex::get_unchecked_mut(start..end, &mut array[..] as *mut [u32]) };
+    }
+}
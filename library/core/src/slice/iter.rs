@@ -3,13 +3,13 @@
 #[macro_use] // import iterator! and forward_iterator!
 mod macros;
 
+use safety::check_invariant;
+
 use super::{from_raw_parts, from_raw_parts_mut};
 use crate::hint::assert_unchecked;
 use crate::iter::{
     FusedIterator, TrustedLen, TrustedRandomAccess, TrustedRandomAccessNoCoerce, UncheckedIterator,
 };
-#[cfg(kani)]
-use crate::kani;
 use crate::marker::PhantomData;
 use crate::mem::{self, SizedTypeProperties};
 use crate::num::NonZero;
@@ -94,6 +94,7 @@ unsafe impl<T: Sync> Sync for Iter<'_, T> {}
 #[stable(feature = "rust1", since = "1.0.0")]
 unsafe impl<T: Sync> Send for Iter<'_, T> {}
 
+#[check_invariant]
 impl<'a, T> Iter<'a, T> {
     #[inline]
     pub(super) const fn new(slice: &'a [T]) -> Self {
@@ -153,6 +154,7 @@ iterator! {struct Iter -> *const T, &'a T, const, {/* no mut */}, as_ref, {
 }}
 
 #[stable(feature = "rust1", since = "1.0.0")]
+#[check_invariant]
 impl<T> Clone for Iter<'_, T> {
     #[inline]
     fn clone(&self) -> Self {
@@ -161,6 +163,7 @@ impl<T> Clone for Iter<'_, T> {
 }
 
 #[stable(feature = "slice_iter_as_ref", since = "1.13.0")]
+#[check_invariant]
 impl<T> AsRef<[T]> for Iter<'_, T> {
     #[inline]
     fn as_ref(&self) -> &[T] {
@@ -3604,6 +3607,13 @@ mod verify {
                     iter.count();
                 }
 
+                #[kani::proof_for_contract(Iter::as_slice)]
+                fn check_as_slice_invariant() {
+                    let array: [$ty; MAX_LEN] = kani::any();
+                    let iter = any_iter::<$ty>(&array);
+                    let _ = iter.as_slice();
+                }
+
                 #[kani::proof]
                 fn check_default() {
                     let iter: Iter<'_, $ty> = Iter::default();
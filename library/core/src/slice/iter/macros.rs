@@ -77,7 +77,7 @@ macro_rules! iterator {
             ///
             /// The iterator must not be empty
             #[inline]
-            #[cfg_attr(kani, kani::modifies(self))]
+            #[safety::modifies(self)]
             #[safety::requires(!is_empty!(self))]
             #[safety::ensures(|_| self.is_safe())]
             unsafe fn next_back_unchecked(&mut self) -> $elem {
@@ -99,7 +99,7 @@ macro_rules! iterator {
             // returning the old start.
             // Unsafe because the offset must not exceed `self.len()`.
             #[inline(always)]
-            #[cfg_attr(kani, kani::modifies(self))]
+            #[safety::modifies(self)]
             #[safety::requires(offset <= len!(self))]
             #[safety::ensures(|_| self.is_safe())]
             unsafe fn post_inc_start(&mut self, offset: usize) -> NonNull<T> {
@@ -121,7 +121,7 @@ macro_rules! iterator {
             // returning the new end.
             // Unsafe because the offset must not exceed `self.len()`.
             #[inline(always)]
-            #[cfg_attr(kani, kani::modifies(self))]
+            #[safety::modifies(self)]
             #[safety::requires(offset <= len!(self))]
             #[safety::ensures(|_| self.is_safe())]
             unsafe fn pre_dec_end(&mut self, offset: usize) -> NonNull<T> {
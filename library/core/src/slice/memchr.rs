@@ -2,6 +2,8 @@
 // Copyright 2015 Andrew Gallant, bluss and Nicolas Koch
 
 use crate::intrinsics::const_eval_select;
+#[cfg(kani)]
+use crate::kani;
 
 const LO_USIZE: usize = usize::repeat_u8(0x01);
 const HI_USIZE: usize = usize::repeat_u8(0x80);
@@ -22,6 +24,10 @@ const fn contains_zero_byte(x: usize) -> bool {
 /// Returns the first index matching the byte `x` in `text`.
 #[inline]
 #[must_use]
+#[safety::ensures(|result: &Option<usize>| match *result {
+    Some(i) => i < text.len() && text[i] == x && safety::forall!(|j in 0..i| text[j] != x),
+    None => !safety::exists!(|j in 0..text.len()| text[j] == x),
+})]
 pub const fn memchr(x: u8, text: &[u8]) -> Option<usize> {
     // Fast path for small slices.
     if text.len() < 2 * USIZE_BYTES {
@@ -159,3 +165,21 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     // Find the byte before the point the body loop stopped.
     text[..offset].iter().rposition(|elt| *elt == x)
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    /// Long enough for both the naive and the word-at-a-time search.
+    const LEN: usize = 32;
+
+    // pub const fn memchr(x: u8, text: &[u8]) -> Option<usize>
+    #[kani::proof_for_contract(memchr)]
+    #[kani::unwind(33)]
+    pub fn check_memchr() {
+        let text: [u8; LEN] = kani::any();
+        let slice = kani::slice::any_slice_of_array(&text);
+        memchr(kani::any(), slice);
+    }
+}
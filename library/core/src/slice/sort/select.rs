@@ -7,6 +7,8 @@
 //! better performance than one would get using heapsort as fallback.
 
 use crate::cfg_select;
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::{self, SizedTypeProperties};
 #[cfg(not(feature = "optimize_for_size"))]
 use crate::slice::sort::shared::pivot::choose_pivot;
@@ -309,3 +311,42 @@ fn median_idx<T, F: FnMut(&T, &T) -> bool>(
     }
     b
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::verify::*;
+
+    fn any_input() -> ([Elem; MAX_LEN], usize, usize) {
+        let len = kani::any_where(|&len: &usize| 0 < len && len <= MAX_LEN);
+        let index = kani::any_where(|&index: &usize| index < len);
+        (any_elems(), len, index)
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_partition_at_index() {
+        let (mut v, len, index) = any_input();
+        partition_at_index(&mut v[..len], index, arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_partition_at_index_total_order() {
+        let (mut v, len, index) = any_input();
+        let (left, pivot, right) = partition_at_index(&mut v[..len], index, key_is_less);
+        assert!(left.iter().all(|elem| elem.key <= pivot.key));
+        assert!(right.iter().all(|elem| elem.key >= pivot.key));
+        assert!(is_permutation(&v[..len]));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    #[kani::should_panic]
+    pub fn check_partition_at_index_panicking_is_less() {
+        let (mut v, len, index) = any_input();
+        partition_at_index(&mut v[..len], index, panicking_is_less);
+    }
+}
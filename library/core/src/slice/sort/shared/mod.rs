@@ -45,3 +45,65 @@ pub(crate) fn find_existing_run<T, F: FnMut(&T, &T) -> bool>(
         (run_len, strictly_descending)
     }
 }
+
+/// Elements and comparison functions shared by the sort harnesses.
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+pub(crate) mod verify {
+    use crate::{array, kani};
+
+    /// The largest number of elements the harnesses sort.
+    pub(crate) const MAX_LEN: usize = 8;
+
+    /// An element with an arbitrary key to sort by, and the position it starts at, to check that
+    /// every element is kept exactly once and that elements with equal keys keep their order.
+    #[derive(Clone, Copy)]
+    pub(crate) struct Elem {
+        pub(crate) key: u8,
+        pub(crate) id: usize,
+    }
+
+    pub(crate) fn any_elems<const N: usize>() -> [Elem; N] {
+        let keys: [u8; N] = kani::any();
+        array::from_fn(|id| Elem { key: keys[id], id })
+    }
+
+    /// A comparison function that answers arbitrarily, so it is neither a strict weak order nor
+    /// consistent between calls.
+    pub(crate) fn arbitrary_is_less(_: &Elem, _: &Elem) -> bool {
+        kani::any()
+    }
+
+    /// Like [`arbitrary_is_less`], but may also panic.
+    ///
+    /// Kani does not model unwinding, so the harnesses that use it only show that no undefined
+    /// behavior happens up to the panic.
+    pub(crate) fn panicking_is_less(_: &Elem, _: &Elem) -> bool {
+        if kani::any() {
+            panic!("is_less panicked");
+        }
+        kani::any()
+    }
+
+    /// A strict total order on the keys.
+    pub(crate) fn key_is_less(a: &Elem, b: &Elem) -> bool {
+        a.key < b.key
+    }
+
+    /// Whether the ids of `elems` are exactly `0..elems.len()`, in any order.
+    pub(crate) fn is_permutation(elems: &[Elem]) -> bool {
+        let mut seen = [false; MAX_LEN];
+        for elem in elems {
+            if elem.id >= elems.len() || seen[elem.id] {
+                return false;
+            }
+            seen[elem.id] = true;
+        }
+        true
+    }
+
+    /// Whether `elems` is sorted by key, with equal keys in the order of their ids.
+    pub(crate) fn is_sorted_stable(elems: &[Elem]) -> bool {
+        elems.windows(2).all(|w| w[0].key < w[1].key || (w[0].key == w[1].key && w[0].id < w[1].id))
+    }
+}
@@ -1,7 +1,13 @@
 //! This module contains a variety of sort implementations that are optimized for small lengths.
 
+use safety::{modifies, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::{self, ManuallyDrop, MaybeUninit};
 use crate::slice::sort::shared::FreezeMarker;
+#[cfg(any(kani, contracts_runtime_checks))]
+use crate::ub_checks;
 use crate::{hint, intrinsics, ptr, slice};
 
 // It's important to differentiate between SMALL_SORT_THRESHOLD performance for
@@ -383,6 +389,17 @@ where
 /// types. `is_less` could be a huge function and we want to give the compiler an option to
 /// not inline this function. For the same reasons that this function is very perf critical
 /// it should be in the same module as the functions that use it.
+#[requires(
+    ub_checks::can_dereference(v_base.wrapping_add(a_pos))
+        && ub_checks::can_write(v_base.wrapping_add(a_pos))
+        && ub_checks::same_allocation(v_base, v_base.wrapping_add(a_pos))
+)]
+#[requires(
+    ub_checks::can_dereference(v_base.wrapping_add(b_pos))
+        && ub_checks::can_write(v_base.wrapping_add(b_pos))
+        && ub_checks::same_allocation(v_base, v_base.wrapping_add(b_pos))
+)]
+#[modifies(v_base.wrapping_add(a_pos), v_base.wrapping_add(b_pos))]
 unsafe fn swap_if_less<T, F>(v_base: *mut T, a_pos: usize, b_pos: usize, is_less: &mut F)
 where
     F: FnMut(&T, &T) -> bool,
@@ -609,6 +626,10 @@ pub fn insertion_sort_shift_left<T, F: FnMut(&T, &T) -> bool>(
 
 /// SAFETY: The caller MUST guarantee that `v_base` is valid for 4 reads and
 /// `dst` is valid for 4 writes. The result will be stored in `dst[0..4]`.
+#[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(v_base, 4)))]
+#[requires(ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, 4)))]
+#[requires(v_base.addr().abs_diff(dst.addr()) >= 4 * size_of::<T>())]
+#[modifies(ptr::slice_from_raw_parts_mut(dst, 4))]
 pub unsafe fn sort4_stable<T, F: FnMut(&T, &T) -> bool>(
     v_base: *const T,
     dst: *mut T,
@@ -757,6 +778,9 @@ unsafe fn merge_down<T, F: FnMut(&T, &T) -> bool>(
 ///
 /// Note that T must be Freeze, the comparison function is evaluated on outdated
 /// temporary 'copies' that may not end up in the final array.
+#[requires(v.len() >= 2 && ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, v.len())))]
+#[requires(v.as_ptr().addr().abs_diff(dst.addr()) >= size_of_val(v))]
+#[modifies(ptr::slice_from_raw_parts_mut(dst, v.len()))]
 unsafe fn bidirectional_merge<T: FreezeMarker, F: FnMut(&T, &T) -> bool>(
     v: &[T],
     dst: *mut T,
@@ -865,3 +889,146 @@ pub(crate) const fn has_efficient_in_place_swap<T>() -> bool {
     // Heuristic that holds true on all tested 64-bit capable architectures.
     size_of::<T>() <= 8 // size_of::<u64>()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::verify::*;
+
+    // unsafe fn swap_if_less<T, F>(v_base: *mut T, a_pos: usize, b_pos: usize, is_less: &mut F)
+    #[kani::proof_for_contract(swap_if_less)]
+    pub fn check_swap_if_less() {
+        let mut v: [Elem; MAX_LEN] = any_elems();
+        let a_pos = kani::any_where(|&a_pos: &usize| a_pos < MAX_LEN);
+        let b_pos = kani::any_where(|&b_pos: &usize| b_pos < MAX_LEN);
+        unsafe { swap_if_less(v.as_mut_ptr(), a_pos, b_pos, &mut arbitrary_is_less) };
+        assert!(is_permutation(&v));
+    }
+
+    #[kani::proof]
+    pub fn check_swap_if_less_total_order() {
+        let mut v: [Elem; 2] = any_elems();
+        unsafe { swap_if_less(v.as_mut_ptr(), 0, 1, &mut key_is_less) };
+        assert!(is_permutation(&v) && is_sorted_stable(&v));
+    }
+
+    // pub unsafe fn sort4_stable<T, F>(v_base: *const T, dst: *mut T, is_less: &mut F)
+    #[kani::proof_for_contract(sort4_stable)]
+    pub fn check_sort4_stable() {
+        let v: [Elem; 4] = any_elems();
+        let mut dst = [const { MaybeUninit::uninit() }; 4];
+        unsafe {
+            sort4_stable(
+                v.as_ptr(),
+                MaybeUninit::slice_as_mut_ptr(&mut dst),
+                &mut arbitrary_is_less,
+            )
+        };
+        assert!(is_permutation(unsafe { dst.assume_init_ref() }));
+    }
+
+    #[kani::proof]
+    pub fn check_sort4_stable_total_order() {
+        let v: [Elem; 4] = any_elems();
+        let mut dst = [const { MaybeUninit::uninit() }; 4];
+        unsafe {
+            sort4_stable(v.as_ptr(), MaybeUninit::slice_as_mut_ptr(&mut dst), &mut key_is_less)
+        };
+        let dst = unsafe { dst.assume_init_ref() };
+        assert!(is_permutation(dst) && is_sorted_stable(dst));
+    }
+
+    #[kani::proof]
+    #[kani::should_panic]
+    pub fn check_sort4_stable_panicking_is_less() {
+        let v: [Elem; 4] = any_elems();
+        let mut dst = [const { MaybeUninit::uninit() }; 4];
+        unsafe {
+            sort4_stable(
+                v.as_ptr(),
+                MaybeUninit::slice_as_mut_ptr(&mut dst),
+                &mut panicking_is_less,
+            )
+        };
+    }
+
+    /// Arbitrary elements and a length of at least 2, as `bidirectional_merge` requires.
+    fn any_merge_input() -> ([Elem; MAX_LEN], usize) {
+        (any_elems(), kani::any_where(|&len: &usize| 2 <= len && len <= MAX_LEN))
+    }
+
+    // unsafe fn bidirectional_merge<T: FreezeMarker, F>(v: &[T], dst: *mut T, is_less: &mut F)
+    // An inconsistent `is_less` may lead `bidirectional_merge` to panic about the violated order,
+    // but never to undefined behavior.
+    #[kani::proof_for_contract(bidirectional_merge)]
+    #[kani::unwind(9)]
+    #[kani::should_panic]
+    pub fn check_bidirectional_merge() {
+        let (v, len) = any_merge_input();
+        let mut dst = [const { MaybeUninit::uninit() }; MAX_LEN];
+        let dst = MaybeUninit::slice_as_mut_ptr(&mut dst);
+        unsafe { bidirectional_merge(&v[..len], dst, &mut arbitrary_is_less) };
+    }
+
+    // unsafe fn bidirectional_merge<T: FreezeMarker, F>(v: &[T], dst: *mut T, is_less: &mut F)
+    #[kani::proof_for_contract(bidirectional_merge)]
+    #[kani::unwind(9)]
+    pub fn check_bidirectional_merge_total_order() {
+        let (v, len) = any_merge_input();
+        let v = &v[..len];
+        kani::assume(is_sorted_stable(&v[..len / 2]) && is_sorted_stable(&v[len / 2..]));
+        let mut dst = [const { MaybeUninit::uninit() }; MAX_LEN];
+        unsafe {
+            bidirectional_merge(v, MaybeUninit::slice_as_mut_ptr(&mut dst), &mut key_is_less)
+        };
+        let dst = unsafe { dst[..len].assume_init_ref() };
+        assert!(is_permutation(dst) && is_sorted_stable(dst));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    #[kani::should_panic]
+    pub fn check_bidirectional_merge_panicking_is_less() {
+        let (v, len) = any_merge_input();
+        let mut dst = [const { MaybeUninit::uninit() }; MAX_LEN];
+        let dst = MaybeUninit::slice_as_mut_ptr(&mut dst);
+        unsafe { bidirectional_merge(&v[..len], dst, &mut panicking_is_less) };
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_sort8_stable_total_order() {
+        let mut v: [Elem; 8] = any_elems();
+        let mut dst = [const { MaybeUninit::uninit() }; 8];
+        let mut scratch = [const { MaybeUninit::uninit() }; 8];
+        unsafe {
+            sort8_stable(
+                v.as_mut_ptr(),
+                MaybeUninit::slice_as_mut_ptr(&mut dst),
+                MaybeUninit::slice_as_mut_ptr(&mut scratch),
+                &mut key_is_less,
+            )
+        };
+        let dst = unsafe { dst.assume_init_ref() };
+        assert!(is_permutation(dst) && is_sorted_stable(dst));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_insertion_sort_shift_left() {
+        let mut v: [Elem; MAX_LEN] = any_elems();
+        let len = kani::any_where(|&len: &usize| 1 <= len && len <= MAX_LEN);
+        let offset = kani::any_where(|&offset: &usize| 1 <= offset && offset <= len);
+        let total_order = kani::any();
+        let v = &mut v[..len];
+        kani::assume(!total_order || is_sorted_stable(&v[..offset]));
+        if total_order {
+            insertion_sort_shift_left(v, offset, &mut key_is_less);
+            assert!(is_sorted_stable(v));
+        } else {
+            insertion_sort_shift_left(v, offset, &mut arbitrary_is_less);
+        }
+        assert!(is_permutation(v));
+    }
+}
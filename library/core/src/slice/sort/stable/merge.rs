@@ -1,7 +1,11 @@
 //! This module contains logic for performing a merge of two sorted sub-slices.
 
-use crate::mem::MaybeUninit;
-use crate::{cmp, ptr};
+use safety::{modifies, requires};
+
+#[cfg(kani)]
+use crate::kani;
+use crate::mem::{MaybeUninit, SizedTypeProperties};
+use crate::{cmp, ptr, ub_checks};
 
 /// Merges non-decreasing runs `v[..mid]` and `v[mid..]` using `scratch` as
 /// temporary storage, and stores the result into `v[..]`.
@@ -75,6 +79,14 @@ impl<T> MergeState<T> {
     /// the longer sub-slice and so that `dst` can be written to at least the shorter sub-slice
     /// length times. In addition `start -> end` and `right -> right_end` MUST be valid to be
     /// read. This function MUST only be called once.
+    #[requires(is_readable_range(self.start, self.end) && is_readable_range(right, right_end))]
+    #[requires(range_len(self.dst, right) == range_len(self.start, self.end))]
+    #[requires(is_writable_range(self.dst, right_end))]
+    #[modifies(self)]
+    #[modifies(ptr::slice_from_raw_parts_mut(
+        self.dst,
+        range_len(self.dst, right_end).unwrap_or(0)
+    ))]
     unsafe fn merge_up<F: FnMut(&T, &T) -> bool>(
         &mut self,
         mut right: *const T,
@@ -105,6 +117,16 @@ impl<T> MergeState<T> {
     /// the shorter sub-slice and so that `out` can be written to at least the shorter sub-slice
     /// length times. In addition `left_end <- dst` and `right_end <- end` MUST be valid to be
     /// read. This function MUST only be called once.
+    #[requires(self.start < self.end && is_readable_range(self.start, self.end))]
+    #[requires(left_end < self.dst as *const T && is_readable_range(left_end, self.dst))]
+    #[requires(right_end == self.start as *const T)]
+    #[requires(range_len(self.dst, out) == range_len(self.start, self.end))]
+    #[requires(is_writable_range(left_end, out))]
+    #[modifies(self)]
+    #[modifies(ptr::slice_from_raw_parts_mut(
+        left_end as *mut T,
+        range_len(left_end, out).unwrap_or(0)
+    ))]
     unsafe fn merge_down<F: FnMut(&T, &T) -> bool>(
         &mut self,
         left_end: *const T,
@@ -148,3 +170,107 @@ impl<T> Drop for MergeState<T> {
         }
     }
 }
+
+/// Returns the number of elements in `start..end`, or `None` if the pointers do not delimit a
+/// range of the same allocation. Only used in contracts.
+#[allow(dead_code)]
+fn range_len<T>(start: *const T, end: *const T) -> Option<usize> {
+    if T::IS_ZST || start > end || !ub_checks::same_allocation(start, end) {
+        return None;
+    }
+    // SAFETY: we just checked that `start <= end` point into the same allocation.
+    Some(unsafe { end.offset_from_unsigned(start) })
+}
+
+/// Whether `start..end` holds initialized elements. Only used in contracts.
+#[allow(dead_code)]
+fn is_readable_range<T>(start: *const T, end: *const T) -> bool {
+    range_len(start, end)
+        .is_some_and(|len| ub_checks::can_dereference(ptr::slice_from_raw_parts(start, len)))
+}
+
+/// Whether `start..end` can be written to. Only used in contracts.
+#[allow(dead_code)]
+fn is_writable_range<T>(start: *const T, end: *const T) -> bool {
+    range_len(start, end).is_some_and(|len| {
+        ub_checks::can_write(ptr::slice_from_raw_parts_mut(start as *mut T, len))
+    })
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::verify::*;
+
+    /// Arbitrary elements, a length and a split point with both runs non-empty.
+    fn any_input() -> ([Elem; MAX_LEN], usize, usize) {
+        let len = kani::any_where(|&len: &usize| 2 <= len && len <= MAX_LEN);
+        let mid = kani::any_where(|&mid: &usize| 0 < mid && mid < len);
+        (any_elems(), len, mid)
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_merge() {
+        let mut v: [Elem; MAX_LEN] = any_elems();
+        let len = kani::any_where(|&len: &usize| len <= MAX_LEN);
+        // Also covers the split points for which nothing is merged.
+        let mid: usize = kani::any();
+        let scratch_len = kani::any_where(|&scratch_len: &usize| scratch_len <= MAX_LEN);
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch[..scratch_len], mid, &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    // unsafe fn merge_up<F: FnMut(&T, &T) -> bool>(&mut self, right: *const T, ...)
+    #[kani::proof_for_contract(MergeState::merge_up)]
+    #[kani::unwind(9)]
+    pub fn check_merge_up() {
+        let (mut v, len, mid) = any_input();
+        kani::assume(mid <= len - mid);
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch, mid, &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    // unsafe fn merge_down<F: FnMut(&T, &T) -> bool>(&mut self, left_end: *const T, ...)
+    #[kani::proof_for_contract(MergeState::merge_down)]
+    #[kani::unwind(9)]
+    pub fn check_merge_down() {
+        let (mut v, len, mid) = any_input();
+        kani::assume(mid > len - mid);
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch, mid, &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_merge_short_scratch() {
+        let (mut v, len, mid) = any_input();
+        let scratch_len = kani::any_where(|&scratch_len: &usize| scratch_len <= MAX_LEN);
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch[..scratch_len], mid, &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_merge_sorted() {
+        let (mut v, len, mid) = any_input();
+        kani::assume(is_sorted_stable(&v[..mid]) && is_sorted_stable(&v[mid..len]));
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch, mid, &mut key_is_less);
+        assert!(is_sorted_stable(&v[..len]));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    #[kani::should_panic]
+    pub fn check_merge_panicking_is_less() {
+        let (mut v, len, mid) = any_input();
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        merge(&mut v[..len], &mut scratch, mid, &mut panicking_is_less);
+    }
+}
@@ -2,6 +2,8 @@
 
 #[cfg(not(any(feature = "optimize_for_size", target_pointer_width = "16")))]
 use crate::cmp;
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::{MaybeUninit, SizedTypeProperties};
 #[cfg(not(any(feature = "optimize_for_size", target_pointer_width = "16")))]
 use crate::slice::sort::shared::smallsort::{
@@ -163,3 +165,57 @@ impl<T, const N: usize> AlignedStorage<T, N> {
         unsafe { core::slice::from_raw_parts_mut(self.storage.as_mut_ptr().cast(), len) }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::verify::*;
+
+    /// A buffer that is never allocated, since the stack buffer of `sort` and `driftsort_main`
+    /// holds more elements than the harnesses sort.
+    struct NoHeapBuf;
+
+    impl BufGuard<Elem> for NoHeapBuf {
+        fn with_capacity(_: usize) -> Self {
+            unreachable!()
+        }
+
+        fn as_uninit_slice_mut(&mut self) -> &mut [MaybeUninit<Elem>] {
+            unreachable!()
+        }
+    }
+
+    fn any_input() -> ([Elem; MAX_LEN], usize) {
+        let len = kani::any_where(|&len: &usize| len <= MAX_LEN);
+        (any_elems(), len)
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_sort() {
+        let (mut v, len) = any_input();
+        sort::<_, _, NoHeapBuf>(&mut v[..len], &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    // `sort` only calls `driftsort_main` for more than `MAX_LEN` elements, so the harnesses call
+    // it directly to keep the inputs bounded.
+    #[cfg(not(any(feature = "optimize_for_size", target_pointer_width = "16")))]
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_driftsort_main() {
+        let (mut v, len) = any_input();
+        driftsort_main::<_, _, NoHeapBuf>(&mut v[..len], &mut arbitrary_is_less);
+        assert!(is_permutation(&v[..len]));
+    }
+
+    #[cfg(not(any(feature = "optimize_for_size", target_pointer_width = "16")))]
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_driftsort_main_sorted() {
+        let (mut v, len) = any_input();
+        driftsort_main::<_, _, NoHeapBuf>(&mut v[..len], &mut key_is_less);
+        assert!(is_sorted_stable(&v[..len]));
+    }
+}
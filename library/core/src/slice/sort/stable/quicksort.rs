@@ -1,9 +1,15 @@
 //! This module contains a stable quicksort and partition implementation.
 
+use safety::{ensures, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::{ManuallyDrop, MaybeUninit};
 use crate::slice::sort::shared::FreezeMarker;
 use crate::slice::sort::shared::pivot::choose_pivot;
 use crate::slice::sort::shared::smallsort::StableSmallSortTypeImpl;
+#[cfg(any(kani, contracts_runtime_checks))]
+use crate::ub_checks;
 use crate::{intrinsics, ptr};
 
 /// Sorts `v` recursively using quicksort.
@@ -87,6 +93,7 @@ pub fn quicksort<T, F: FnMut(&T, &T) -> bool>(
 ///
 /// If `is_less` is not a strict total order or panics, `scratch.len() < v.len()`,
 /// or `pivot_pos >= v.len()`, the result and `v`'s state is sound but unspecified.
+#[ensures(|num_left: &usize| *num_left <= v.len())]
 fn stable_partition<T, F: FnMut(&T, &T) -> bool>(
     v: &mut [T],
     scratch: &mut [MaybeUninit<T>],
@@ -199,6 +206,9 @@ impl<T> PartitionState<T> {
     ///
     /// `scan` and `scratch` must point to valid disjoint buffers of length `len`. The
     /// scan buffer must be initialized.
+    #[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(scan, len)))]
+    #[requires(ub_checks::can_write(ptr::slice_from_raw_parts_mut(scratch, len)))]
+    #[requires(scan.addr().abs_diff(scratch.addr()) >= len * size_of::<T>())]
     unsafe fn new(scan: *const T, scratch: *mut T, len: usize) -> Self {
         // SAFETY: See function safety comment.
         unsafe { Self { scratch_base: scratch, scan, num_left: 0, scratch_rev: scratch.add(len) } }
@@ -213,6 +223,7 @@ impl<T> PartitionState<T> {
     /// This function may be called at most `len` times. If it is called exactly
     /// `len` times the scratch buffer then contains a copy of each element from
     /// the scan buffer exactly once - a permutation, and num_left <= len.
+    #[requires(self.scratch_base < self.scratch_rev && ub_checks::can_dereference(self.scan))]
     unsafe fn partition_one(&mut self, towards_left: bool) -> *mut T {
         // SAFETY: see individual comments.
         unsafe {
@@ -257,3 +268,89 @@ fn has_direct_interior_mutability<T>() -> bool {
     // Otherwise a type like Mutex<Option<Box<str>>> could lead to double free.
     !T::is_freeze()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::verify::*;
+
+    fn any_input() -> ([Elem; MAX_LEN], usize, usize, bool) {
+        let len = kani::any_where(|&len: &usize| 0 < len && len <= MAX_LEN);
+        let pivot_pos = kani::any_where(|&pivot_pos: &usize| pivot_pos < len);
+        (any_elems(), len, pivot_pos, kani::any())
+    }
+
+    // fn stable_partition<T, F>(v: &mut [T], scratch: &mut [MaybeUninit<T>], ...) -> usize
+    #[kani::proof_for_contract(stable_partition)]
+    #[kani::unwind(9)]
+    pub fn check_stable_partition() {
+        let (mut v, len, pivot_pos, pivot_goes_left) = any_input();
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        let v = &mut v[..len];
+        stable_partition(v, &mut scratch, pivot_pos, pivot_goes_left, &mut arbitrary_is_less);
+        assert!(is_permutation(v));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    pub fn check_stable_partition_total_order() {
+        let (mut v, len, pivot_pos, pivot_goes_left) = any_input();
+        let pivot = v[pivot_pos];
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        let v = &mut v[..len];
+        let num_left =
+            stable_partition(v, &mut scratch, pivot_pos, pivot_goes_left, &mut key_is_less);
+        assert!(is_permutation(v));
+        for (i, elem) in v.iter().enumerate() {
+            let goes_left =
+                if elem.id == pivot.id { pivot_goes_left } else { elem.key < pivot.key };
+            assert_eq!(goes_left, i < num_left);
+        }
+        // Both sides keep the original order of their elements.
+        assert!(v[..num_left].windows(2).all(|w| w[0].id < w[1].id));
+        assert!(v[num_left..].windows(2).all(|w| w[0].id < w[1].id));
+    }
+
+    // unsafe fn new(scan: *const T, scratch: *mut T, len: usize) -> Self
+    #[kani::proof_for_contract(PartitionState::new)]
+    pub fn check_partition_state_new() {
+        let (v, len, _, _) = any_input();
+        let mut scratch = [const { MaybeUninit::<Elem>::uninit() }; MAX_LEN];
+        let scratch_base = MaybeUninit::slice_as_mut_ptr(&mut scratch);
+        let state = unsafe { PartitionState::new(v.as_ptr(), scratch_base, len) };
+        assert_eq!(state.num_left, 0);
+        assert!(ptr::eq(state.scratch_rev, scratch_base.wrapping_add(len)));
+    }
+
+    // unsafe fn partition_one(&mut self, towards_left: bool) -> *mut T
+    #[kani::proof_for_contract(PartitionState::partition_one)]
+    #[kani::unwind(9)]
+    pub fn check_partition_one() {
+        let (v, len, _, _) = any_input();
+        let mut scratch = [const { MaybeUninit::<Elem>::uninit() }; MAX_LEN];
+        let scratch_base = MaybeUninit::slice_as_mut_ptr(&mut scratch);
+        let mut state = unsafe { PartitionState::new(v.as_ptr(), scratch_base, len) };
+        let calls = kani::any_where(|&calls: &usize| 0 < calls && calls <= len);
+        for _ in 0..calls {
+            let dst = unsafe { state.partition_one(kani::any()) };
+            assert!(scratch_base <= dst && dst < scratch_base.wrapping_add(len));
+        }
+        assert!(state.num_left <= calls);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    #[kani::should_panic]
+    pub fn check_stable_partition_panicking_is_less() {
+        let (mut v, len, pivot_pos, pivot_goes_left) = any_input();
+        let mut scratch = [const { MaybeUninit::uninit() }; MAX_LEN];
+        stable_partition(
+            &mut v[..len],
+            &mut scratch,
+            pivot_pos,
+            pivot_goes_left,
+            &mut panicking_is_less,
+        );
+    }
+}
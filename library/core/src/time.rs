@@ -19,7 +19,7 @@
 //! assert_eq!(total, Duration::new(10, 7));
 //! ```
 
-use safety::{Invariant, ensures};
+use safety::{Invariant, check_invariant, ensures};
 
 use crate::fmt;
 use crate::iter::Sum;
@@ -96,6 +96,7 @@ pub struct Duration {
     nanos: Nanoseconds, // Always 0 <= nanos < NANOS_PER_SEC
 }
 
+#[check_invariant]
 impl Duration {
     /// The duration of one second.
     ///
@@ -1574,6 +1575,7 @@ macro_rules! try_from_secs {
     }};
 }
 
+#[check_invariant]
 impl Duration {
     /// The checked version of [`from_secs_f32`].
     ///
@@ -1906,4 +1908,17 @@ pub mod duration_verify {
         let amt = kani::any::<u32>();
         let _ = d0.checked_div(amt);
     }
+
+    #[kani::proof_for_contract(Duration::is_zero)]
+    fn duration_is_zero() {
+        let dur = safe_duration();
+        let _ = dur.is_zero();
+    }
+
+    #[kani::proof_for_contract(Duration::abs_diff)]
+    fn duration_abs_diff() {
+        let d0 = safe_duration();
+        let d1 = safe_duration();
+        let _ = d0.abs_diff(d1);
+    }
 }
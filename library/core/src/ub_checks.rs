@@ -2,6 +2,9 @@
 //! common preconditions.
 
 use crate::intrinsics::{self, const_eval_select};
+use crate::marker::PhantomData;
+use crate::mem::ManuallyDrop;
+use crate::ptr::{NonNull, Unique};
 
 /// Checks that the preconditions of an unsafe function are followed.
 ///
@@ -171,14 +174,64 @@ pub(crate) const fn maybe_is_nonoverlapping(
     )
 }
 
+/// Checks a contract clause, i.e., a precondition or a loop invariant, at runtime.
+///
+/// Calls to this function are inserted by the `safety` contract attributes when the `safety`
+/// crate is compiled with `--cfg contracts_runtime_checks`. The condition is not evaluated during
+/// const evaluation, which allows contracts of `const fn` to call non-const predicates.
+#[inline]
+#[rustc_const_stable_indirect]
+#[rustc_allow_const_fn_unstable(const_eval_select)]
+pub const fn check_contract<C: Fn() -> bool + Copy>(cond: C, msg: &'static str) {
+    const_eval_select!(
+        @capture[C: Fn() -> bool + Copy] { cond: C, msg: &'static str } :
+        if const {
+            // Contracts are not checked during const evaluation.
+        } else {
+            if !cond() {
+                crate::panicking::panic_nounwind(msg);
+            }
+        }
+    )
+}
+
+/// Checks a postcondition against the value returned by a function, and returns that value.
+///
+/// See [`check_contract`] for when calls to this function are inserted.
+#[inline]
+#[rustc_const_stable_indirect]
+#[rustc_allow_const_fn_unstable(const_eval_select)]
+pub const fn check_contract_ensures<Ret, C: Fn(&Ret) -> bool + Copy>(
+    ret: Ret,
+    cond: C,
+    msg: &'static str,
+) -> Ret {
+    const_eval_select!(
+        @capture[Ret, C: Fn(&Ret) -> bool + Copy] { ret: Ret, cond: C, msg: &'static str } -> Ret :
+        if const {
+            // Contracts are not checked during const evaluation.
+            ret
+        } else {
+            if !cond(&ret) {
+                crate::panicking::panic_nounwind(msg);
+            }
+            ret
+        }
+    )
+}
+
 pub use predicates::*;
 
 /// Provide a few predicates to be used in safety contracts.
 ///
-/// At runtime, they are no-op, and always return true.
-/// FIXME: In some cases, we could do better, for example check if not null and aligned.
+/// At runtime, they perform best-effort checks: pointers must be non-null and properly aligned
+/// unless they point to a zero-sized value, and slices must not be larger than `isize::MAX` bytes.
+/// When running under Miri, pointers to non-zero-sized values must also have provenance.
+/// Whether the memory is actually allocated and initialized cannot be checked at runtime.
 #[cfg(not(kani))]
 mod predicates {
+    use crate::mem::{align_of_val_raw, size_of_val_raw};
+
     /// Checks if a pointer can be dereferenced, ensuring:
     ///   * `src` is valid for reads (see [`crate::ptr`] documentation).
     ///   * `src` is properly aligned (use `read_unaligned` if not).
@@ -186,8 +239,7 @@ mod predicates {
     ///
     /// [`crate::ptr`]: https://doc.rust-lang.org/std/ptr/index.html
     pub fn can_dereference<T: ?Sized>(src: *const T) -> bool {
-        let _ = src;
-        true
+        is_accessible(src, true)
     }
 
     /// Check if a pointer can be written to:
@@ -195,29 +247,44 @@ mod predicates {
     /// * `dst` must be properly aligned. Use `write_unaligned` if this is not the
     ///    case.
     pub fn can_write<T: ?Sized>(dst: *mut T) -> bool {
-        let _ = dst;
-        true
+        is_accessible(dst, true)
     }
 
     /// Check if a pointer can be the target of unaligned reads.
     /// * `src` must be valid for reads.
     /// * `src` must point to a properly initialized value of type `T`.
     pub fn can_read_unaligned<T: ?Sized>(src: *const T) -> bool {
-        let _ = src;
-        true
+        is_accessible(src, false)
     }
 
     /// Check if a pointer can be the target of unaligned writes.
     /// * `dst` must be valid for writes.
     pub fn can_write_unaligned<T: ?Sized>(dst: *mut T) -> bool {
-        let _ = dst;
-        true
+        is_accessible(dst, false)
     }
 
     /// Checks if two pointers point to the same allocation.
+    ///
+    /// This can only be checked when running under Miri, by comparing the provenance of
+    /// the pointers. Equal pointers are always considered to be in the same allocation, since
+    /// they delimit an empty range even if they have no provenance.
     pub fn same_allocation<T: ?Sized>(src: *const T, dst: *const T) -> bool {
-        let _ = (src, dst);
-        true
+        #[cfg(miri)]
+        {
+            if src.addr() == dst.addr() {
+                return true;
+            }
+            match (miri::alloc_id(src.cast()), miri::alloc_id(dst.cast())) {
+                (Some(src_id), Some(dst_id)) => src_id == dst_id,
+                // A pointer without provenance is not in any allocation.
+                _ => false,
+            }
+        }
+        #[cfg(not(miri))]
+        {
+            let _ = (src, dst);
+            true
+        }
     }
 
     /// Check if a float is representable in the given integer type
@@ -228,6 +295,79 @@ mod predicates {
         let _ = value;
         true
     }
+
+    /// Checks the properties of `ptr` that can be checked at runtime before accessing the
+    /// value it points to, with or without alignment requirement.
+    fn is_accessible<T: ?Sized>(ptr: *const T, aligned: bool) -> bool {
+        if !T::has_valid_size(ptr) {
+            return false;
+        }
+        // SAFETY: We just checked that the size of the value fits in `isize`, and the metadata
+        // of raw pointers to trait objects is always a valid vtable.
+        let (size, align) = unsafe { (size_of_val_raw(ptr), align_of_val_raw(ptr)) };
+        if aligned && !ptr.is_aligned_to(align) {
+            return false;
+        }
+        // Every pointer is valid for accesses of size zero.
+        if size == 0 {
+            return true;
+        }
+        #[cfg(miri)]
+        {
+            if miri::alloc_id(ptr.cast()).is_none() {
+                return false;
+            }
+        }
+        !ptr.is_null()
+    }
+
+    /// Checks that the size of the value a pointer points to does not exceed `isize::MAX`.
+    trait PointeeSize {
+        fn has_valid_size(ptr: *const Self) -> bool;
+    }
+
+    impl<T: ?Sized> PointeeSize for T {
+        default fn has_valid_size(_ptr: *const Self) -> bool {
+            // The size of sized types and trait objects always fits in `isize`.
+            true
+        }
+    }
+
+    impl<T> PointeeSize for [T] {
+        fn has_valid_size(ptr: *const Self) -> bool {
+            super::is_valid_allocation_size(size_of::<T>(), ptr.len())
+        }
+    }
+
+    impl PointeeSize for str {
+        fn has_valid_size(ptr: *const Self) -> bool {
+            super::is_valid_allocation_size(1, (ptr as *const [u8]).len())
+        }
+    }
+
+    #[cfg(miri)]
+    mod miri {
+        unsafe extern "Rust" {
+            /// Miri-provided extern function to get the identifier of the allocation a pointer
+            /// points to. Aborts interpretation if the pointer does not have provenance.
+            fn miri_get_alloc_id(ptr: *const ()) -> u64;
+        }
+
+        /// Miri gives allocations addresses from 32 pages up, so the pointers below, like
+        /// `NonNull::dangling()` and the pointers created by `ptr::without_provenance`, cannot
+        /// have provenance.
+        const MIN_ALLOCATION_ADDR: usize = 32 * 4096;
+
+        /// Returns the identifier of the allocation `ptr` points to, or `None` if `ptr` is known
+        /// not to have provenance.
+        pub(super) fn alloc_id(ptr: *const ()) -> Option<u64> {
+            if ptr.addr() < MIN_ALLOCATION_ADDR {
+                return None;
+            }
+            // SAFETY: this call is always safe.
+            Some(unsafe { miri_get_alloc_id(ptr) })
+        }
+    }
 }
 
 #[cfg(kani)]
@@ -260,6 +400,14 @@ pub trait Invariant {
 
 /// Any value is considered safe for the type
 macro_rules! trivial_invariant {
+    ( <$param: ident> $type: ty ) => {
+        impl<$param: ?Sized> Invariant for $type {
+            #[inline(always)]
+            fn is_safe(&self) -> bool {
+                true
+            }
+        }
+    };
     ( $type: ty ) => {
         impl Invariant for $type {
             #[inline(always)]
@@ -270,6 +418,18 @@ macro_rules! trivial_invariant {
     };
 }
 
+/// A tuple is safe if all of its elements are safe
+macro_rules! tuple_invariant {
+    ( $($name: ident $idx: tt),+ ) => {
+        impl<$($name: Invariant),+> Invariant for ($($name,)+) {
+            #[inline]
+            fn is_safe(&self) -> bool {
+                $(self.$idx.is_safe())&&+
+            }
+        }
+    };
+}
+
 trivial_invariant!(u8);
 trivial_invariant!(u16);
 trivial_invariant!(u32);
@@ -292,3 +452,66 @@ trivial_invariant!(f16);
 trivial_invariant!(f32);
 trivial_invariant!(f64);
 trivial_invariant!(f128);
+
+trivial_invariant!(<T> PhantomData<T>);
+
+// Pointers do not have a safety invariant, the memory they point to only needs to be valid
+// when they are dereferenced.
+trivial_invariant!(<T> *const T);
+trivial_invariant!(<T> *mut T);
+trivial_invariant!(<T> NonNull<T>);
+trivial_invariant!(<T> Unique<T>);
+
+tuple_invariant!(A 0);
+tuple_invariant!(A 0, B 1);
+tuple_invariant!(A 0, B 1, C 2);
+tuple_invariant!(A 0, B 1, C 2, D 3);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_invariant!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<T: Invariant, const N: usize> Invariant for [T; N] {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.iter().all(Invariant::is_safe)
+    }
+}
+
+impl<T: Invariant> Invariant for [T] {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.iter().all(Invariant::is_safe)
+    }
+}
+
+impl<T: Invariant> Invariant for Option<T> {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        match self {
+            Some(value) => value.is_safe(),
+            None => true,
+        }
+    }
+}
+
+impl<T: Invariant, E: Invariant> Invariant for Result<T, E> {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        match self {
+            Ok(value) => value.is_safe(),
+            Err(err) => err.is_safe(),
+        }
+    }
+}
+
+impl<T: ?Sized + Invariant> Invariant for ManuallyDrop<T> {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        (**self).is_safe()
+    }
+}
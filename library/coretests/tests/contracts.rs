@@ -0,0 +1,135 @@
+//! When the library is built with `--cfg contracts_runtime_checks`, the contracts of these
+//! functions are checked at runtime. A violated contract aborts, so these tests only cover
+//! calls that satisfy the contracts, except for `violated_contract_aborts`, which runs in a
+//! child process.
+use core::alloc::Layout;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+#[test]
+fn ensures_early_return_in_const_fn() {
+    // `Layout::array` returns from a nested function.
+    assert_eq!(Layout::array::<u64>(3).unwrap(), Layout::new::<[u64; 3]>());
+    assert!(Layout::array::<u64>(usize::MAX).is_err());
+
+    const LAYOUT: Layout = match Layout::array::<u16>(4) {
+        Ok(layout) => layout,
+        Err(_) => panic!(),
+    };
+    assert_eq!(LAYOUT.size(), 8);
+}
+
+#[test]
+fn ensures_tuple_pattern() {
+    let x = [1u8, 2, 3];
+    let ptr = NonNull::from(&x[..]);
+    let (data, len) = ptr.to_raw_parts();
+    assert_eq!(data, ptr.cast::<()>());
+    assert_eq!(len, 3);
+}
+
+#[test]
+fn ensures_mut_ref_result() {
+    let mut buf = [MaybeUninit::<u32>::uninit(); 3];
+    let init = buf.write_clone_of_slice(&[1, 2, 3]);
+    init[0] = 4;
+    assert_eq!(init, [4, 2, 3]);
+}
+
+#[test]
+fn requires_in_unsafe_fn() {
+    let mut x = [1u16, 2];
+    let ptr = NonNull::from(&mut x).cast::<u16>();
+    // SAFETY: Both pointers are valid for reads and writes.
+    unsafe {
+        ptr.swap(ptr.add(1));
+        assert_eq!(ptr.read(), 2);
+    }
+    assert_eq!(x, [2, 1]);
+}
+
+#[test]
+fn quantifiers() {
+    let bytes = [1u8, 2, 3];
+    assert!(safety::forall!(|i in 0..bytes.len()| bytes[i] > 0));
+    assert!(!safety::forall!(|i in 0..bytes.len()| bytes[i] > 1));
+    assert!(safety::exists!(|i in 1..bytes.len()| bytes[i] == 3));
+    assert!(!safety::exists!(|i in 0..1| bytes[i] == 3));
+    // Empty ranges.
+    assert!(safety::forall!(|i in 0..0| bytes[i] == 0));
+    assert!(!safety::exists!(|i in 3..3| bytes[i] == 0));
+    // A bitwise or in the bounds is parenthesized.
+    assert!(safety::forall!(|i in 0..(2 | 1)| bytes[i] == i as u8 + 1));
+}
+
+#[test]
+fn quantifiers_in_contracts() {
+    // `memchr` is checked with both quantifiers.
+    assert!(b"abc".contains(&b'c'));
+    assert!(!b"abc".contains(&b'd'));
+    assert!(!b"".contains(&b'a'));
+    // The precondition of `as_ascii_unchecked` is checked with `forall!`.
+    assert_eq!(b"abc"[..].as_ascii().map(<[_]>::len), Some(3));
+    assert_eq!(b"ab\xff"[..].as_ascii(), None);
+}
+
+#[test]
+#[cfg(contracts_runtime_checks)]
+fn violated_contract_aborts() {
+    const VIOLATE: &str = "CORETESTS_VIOLATE_CONTRACT";
+    if std::env::var_os(VIOLATE).is_some() {
+        // SAFETY: not sound, the precondition check aborts before the call.
+        let chars = unsafe { b"ab\xff"[..].as_ascii_unchecked() };
+        assert_eq!(chars.len(), 3);
+        return;
+    }
+
+    // Runs this test again in a child process, which violates the contract.
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "contracts::violated_contract_aborts", "--nocapture"])
+        .env(VIOLATE, "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stderr}");
+    assert!(stderr.contains("as_ascii_unchecked: precondition violated"), "{stderr}");
+}
+
+struct Stack {
+    values: [u32; 4],
+    len: usize,
+}
+
+impl Stack {
+    const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[safety::requires(self.len() < 4)]
+    #[safety::ensures(|_| self.len() == old(self.len()) + 1)]
+    fn push(&mut self, value: u32) {
+        self.values[self.len] = value;
+        self.len += 1;
+    }
+
+    #[safety::ensures(|result| result.is_none() == (old(self.len()) == 0))]
+    #[safety::ensures(|_| self.len() == old(self.len()).saturating_sub(1))]
+    const fn pop(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.values[self.len])
+    }
+}
+
+#[test]
+fn ensures_old_len() {
+    let mut stack = Stack { values: [0; 4], len: 0 };
+    stack.push(1);
+    stack.push(2);
+    assert_eq!(stack.pop(), Some(2));
+    assert_eq!(stack.pop(), Some(1));
+    assert_eq!(stack.pop(), None);
+    assert_eq!(stack.len(), 0);
+}
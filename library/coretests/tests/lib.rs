@@ -93,6 +93,7 @@
 #![feature(try_blocks)]
 #![feature(try_find)]
 #![feature(try_trait_v2)]
+#![feature(ub_checks)]
 #![feature(unsize)]
 #![feature(unwrap_infallible)]
 // tidy-alphabetical-end
@@ -150,6 +151,7 @@ mod char;
 mod clone;
 mod cmp;
 mod const_ptr;
+mod contracts;
 mod convert;
 mod ffi;
 mod floats;
@@ -182,6 +184,7 @@ mod str_lossy;
 mod task;
 mod time;
 mod tuple;
+mod ub_checks;
 mod unicode;
 mod waker;
 
@@ -0,0 +1,83 @@
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::{self, NonNull};
+use core::ub_checks::{
+    Invariant, can_dereference, can_read_unaligned, can_write, can_write_unaligned,
+    same_allocation,
+};
+
+#[test]
+fn can_dereference_valid() {
+    let x = 42u32;
+    assert!(can_dereference(&x as *const u32));
+    let s = [1u16, 2, 3];
+    assert!(can_dereference(&s as *const [u16]));
+    assert!(can_dereference("hello" as *const str));
+}
+
+#[test]
+fn can_dereference_null() {
+    assert!(!can_dereference(ptr::null::<u32>()));
+    assert!(!can_write(ptr::null_mut::<u32>()));
+    assert!(!can_read_unaligned(ptr::null::<u32>()));
+    assert!(!can_write_unaligned(ptr::null_mut::<u32>()));
+}
+
+#[test]
+fn can_dereference_zst() {
+    assert!(can_dereference(ptr::null::<()>()));
+    assert!(can_dereference(ptr::dangling::<[u64; 0]>()));
+    assert!(can_dereference(ptr::slice_from_raw_parts(ptr::dangling::<u32>(), 0)));
+}
+
+#[test]
+fn can_dereference_misaligned() {
+    let x = [0u64; 2];
+    let misaligned = x.as_ptr().cast::<u8>().wrapping_add(1).cast::<u64>();
+    assert!(!can_dereference(misaligned));
+    assert!(!can_write(misaligned.cast_mut()));
+    assert!(can_read_unaligned(misaligned));
+    assert!(can_write_unaligned(misaligned.cast_mut()));
+}
+
+#[test]
+fn can_dereference_oversized_slice() {
+    let x = 0u16;
+    let oversized = ptr::slice_from_raw_parts(&x as *const u16, isize::MAX as usize);
+    assert!(!can_dereference(oversized));
+    assert!(!can_read_unaligned(oversized));
+}
+
+#[test]
+fn same_allocation_in_bounds() {
+    let s = [1u8, 2, 3];
+    assert!(same_allocation(&s[0] as *const u8, &s[2] as *const u8));
+}
+
+struct Even(u32);
+
+impl Invariant for Even {
+    fn is_safe(&self) -> bool {
+        self.0 % 2 == 0
+    }
+}
+
+#[test]
+fn invariant_structural() {
+    assert!([Even(0), Even(2)].is_safe());
+    assert!(![Even(0), Even(1)].is_safe());
+    assert!(![Even(2), Even(3)][..].is_safe());
+    assert!((Even(0), 1u8, Even(4)).is_safe());
+    assert!(!(Even(0), 1u8, Even(5)).is_safe());
+    assert!(Some(Even(2)).is_safe() && None::<Even>.is_safe() && !Some(Even(1)).is_safe());
+    assert!(Ok::<Even, Even>(Even(2)).is_safe() && !Err::<Even, Even>(Even(1)).is_safe());
+    assert!(!ManuallyDrop::new(Even(1)).is_safe());
+}
+
+#[test]
+fn invariant_trivial() {
+    assert!(ptr::null::<Even>().is_safe());
+    assert!(ptr::null_mut::<Even>().is_safe());
+    assert!(NonNull::<Even>::dangling().is_safe());
+    assert!(PhantomData::<Even>.is_safe());
+}
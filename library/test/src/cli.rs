@@ -3,8 +3,9 @@
 use std::env;
 use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::options::{ColorConfig, Options, OutputFormat, RunIgnored, TestShard};
 use super::time::TestTimeOptions;
 
 #[derive(Debug)]
@@ -23,9 +24,16 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub shuffle: bool,
     pub shuffle_seed: Option<u64>,
+    pub shard: Option<TestShard>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Time after which a test that is still running fails.
+    pub test_timeout: Option<Duration>,
+    /// Number of times a failing test is run again.
+    pub retries: usize,
+    /// Whether tests that only pass when retried are a success.
+    pub allow_flaky: bool,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
@@ -102,8 +110,10 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP version 14 document;
+            github = Annotate failures in GitHub Actions",
+            "pretty|terse|json|junit|tap|github",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -142,12 +152,38 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Fail tests that are still running after SECS seconds.
+
+            Tests run in a child process (with panic=abort) are killed. Tests run
+            in-process cannot be stopped: they are abandoned, no further tests are
+            started, and the test harness exits once the other running tests finish.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failing tests again, up to N times. Tests that pass when retried are
+            reported as flaky, and fail the test run unless --allow-flaky is passed.
+
+            Only tests defined with #[test] can be retried.",
+            "N",
+        )
+        .optflag("", "allow-flaky", "Treat tests that pass when retried as a success")
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
             "shuffle-seed",
             "Run tests in random order; seed the random number generator with SEED",
             "SEED",
+        )
+        .optopt(
+            "",
+            "shard",
+            "Run only the tests of shard INDEX, out of COUNT shards that the tests are split into",
+            "INDEX/COUNT",
         );
     opts
 }
@@ -171,6 +207,11 @@ RUST_TEST_SHUFFLE to run the tests in random order. Pass the generated
 tests in the same order again. Note that --shuffle and --shuffle-seed do not
 affect whether the tests are run in parallel.
 
+Use --shard INDEX/COUNT or set RUST_TEST_SHARD to split the tests into COUNT
+shards and only run shard INDEX, which is between 1 and COUNT. Tests are
+assigned to shards by hashing their names, so running every shard runs every
+test exactly once, and the split is the same across runs.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --no-capture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
@@ -262,8 +303,12 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let allow_flaky = unstable_optflag!(matches, allow_unstable, "allow-flaky");
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -303,9 +348,13 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         format,
         shuffle,
         shuffle_seed,
+        shard,
         test_threads,
         skip,
         time_options,
+        test_timeout,
+        retries,
+        allow_flaky,
         options,
         fail_fast: false,
     };
@@ -343,6 +392,40 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(secs_str) => match secs_str.parse::<u64>() {
+            Ok(secs) if secs > 0 => Some(Duration::from_secs(secs)),
+            _ => {
+                return Err(format!(
+                    "argument for --test-timeout must be a positive number of seconds \
+                     (was {secs_str})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!("argument for --retries must be a number (error: {e})"));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
 fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
     let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
     if !shuffle && allow_unstable {
@@ -382,6 +465,39 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn parse_shard(shard_str: &str) -> Option<TestShard> {
+    let (index, count) = shard_str.split_once('/')?;
+    let shard = TestShard { index: index.parse().ok()?, count: count.parse().ok()? };
+    (1 <= shard.index && shard.index <= shard.count).then_some(shard)
+}
+
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<TestShard>> {
+    let mut shard = match unstable_optopt!(matches, allow_unstable, "shard") {
+        Some(shard_str) => match parse_shard(&shard_str) {
+            Some(shard) => Some(shard),
+            None => {
+                return Err(format!(
+                    "argument for --shard must be INDEX/COUNT, with 1 <= INDEX <= COUNT \
+                     (was {shard_str})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if shard.is_none() && allow_unstable {
+        shard = match env::var("RUST_TEST_SHARD") {
+            Ok(val) => match parse_shard(&val) {
+                Some(shard) => Some(shard),
+                None => panic!("RUST_TEST_SHARD is `{val}`, should be INDEX/COUNT."),
+            },
+            Err(_) => None,
+        };
+    }
+
+    Ok(shard)
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -421,9 +537,21 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
+        Some("github") => {
+            if !allow_unstable {
+                return Err("The \"github\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Github
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit, tap or github (was \
                  {v})"
             ));
         }
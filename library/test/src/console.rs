@@ -9,7 +9,8 @@ use super::bench::fmt_bench_samples;
 use super::cli::TestOpts;
 use super::event::{CompletedTest, TestEvent};
 use super::formatters::{
-    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter,
+    GithubFormatter, JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter,
+    TerseFormatter, retries_noun,
 };
 use super::helpers::concurrency::get_concurrency;
 use super::helpers::metrics::MetricMap;
@@ -79,6 +80,7 @@ pub(crate) struct ConsoleTestState {
     pub total: usize,
     pub passed: usize,
     pub failed: usize,
+    pub flaky: usize,
     pub ignored: usize,
     pub filtered_out: usize,
     pub measured: usize,
@@ -88,6 +90,8 @@ pub(crate) struct ConsoleTestState {
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flaky_tests: Vec<(TestDesc, Vec<u8>)>,
+    pub allow_flaky: bool,
     pub options: Options,
 }
 
@@ -103,6 +107,7 @@ impl ConsoleTestState {
             total: 0,
             passed: 0,
             failed: 0,
+            flaky: 0,
             ignored: 0,
             filtered_out: 0,
             measured: 0,
@@ -112,6 +117,8 @@ impl ConsoleTestState {
             not_failures: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            flaky_tests: Vec::new(),
+            allow_flaky: opts.allow_flaky,
             options: opts.options,
         })
     }
@@ -154,6 +161,10 @@ impl ConsoleTestState {
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrFlaky(retries) => {
+                        let noun = retries_noun(retries);
+                        format!("flaky (passed after {retries} {noun})")
+                    }
                 },
                 name,
             )
@@ -165,7 +176,22 @@ impl ConsoleTestState {
     }
 
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.failed + self.flaky + self.ignored + self.measured
+    }
+
+    /// Whether the test run succeeded: no test failed, and flaky tests are allowed if any.
+    pub(crate) fn is_success(&self) -> bool {
+        self.failed == 0 && (self.flaky == 0 || self.allow_flaky)
+    }
+
+    /// The counts of the summary line, e.g. `2 passed; 0 failed; 1 ignored; 0 measured;
+    /// 0 filtered out`. Flaky tests are only counted if there are any.
+    pub(crate) fn summary_counts(&self) -> String {
+        let flaky = if self.flaky > 0 { format!("{} flaky; ", self.flaky) } else { String::new() };
+        format!(
+            "{} passed; {} failed; {flaky}{} ignored; {} measured; {} filtered out",
+            self.passed, self.failed, self.ignored, self.measured, self.filtered_out
+        )
     }
 }
 
@@ -177,7 +203,7 @@ pub(crate) fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) ->
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap | OutputFormat::Github => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
@@ -246,6 +272,10 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flaky_tests.push((test, stdout));
+        }
     }
 }
 
@@ -310,6 +340,8 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
+        OutputFormat::Github => Box::new(GithubFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -323,7 +355,11 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
     run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
     st.exec_time = start_time.map(|t| TestSuiteExecTime(t.elapsed()));
 
-    assert!(opts.fail_fast || st.current_test_count() == st.total);
+    // Not all tests are run with `--fail-fast`, or once an in-process test is abandoned after
+    // `--test-timeout`.
+    let stopped_early =
+        opts.fail_fast || (opts.test_timeout.is_some() && !st.time_failures.is_empty());
+    assert!(stopped_early || st.current_test_count() == st.total);
 
     out.write_run_finish(&st)
 }
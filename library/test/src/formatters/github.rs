@@ -0,0 +1,212 @@
+use std::io;
+use std::io::prelude::Write;
+
+use super::{OutputFormatter, retries_noun};
+use crate::bench::fmt_bench_samples;
+use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::test_result::TestResult;
+use crate::time;
+use crate::types::TestDesc;
+
+/// Formatter for GitHub Actions logs.
+///
+/// Results are written like the pretty formatter, and every failure is reported with an
+/// `::error` workflow command, so that it is annotated on the line that panicked.
+pub(crate) struct GithubFormatter<T> {
+    out: OutputLocation<T>,
+}
+
+impl<T: Write> GithubFormatter<T> {
+    pub(crate) fn new(out: OutputLocation<T>) -> Self {
+        Self { out }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_plain<S: AsRef<str>>(&mut self, s: S) -> io::Result<()> {
+        let s = s.as_ref();
+        self.out.write_all(s.as_bytes())?;
+        self.out.flush()
+    }
+
+    fn write_error(&mut self, desc: &TestDesc, reason: &str, stdout: &[u8]) -> io::Result<()> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let panic = find_panic(&stdout);
+
+        // Annotate the panic location, or the test itself if it didn't panic.
+        let location = match panic {
+            Some(PanicInfo { file, line, col, .. }) => Some((file, line, Some(col))),
+            None if !desc.source_file.is_empty() => {
+                Some((desc.source_file, desc.start_line, Some(desc.start_col)))
+            }
+            None => None,
+        };
+        let mut properties = Vec::new();
+        if let Some((file, line, col)) = location {
+            properties.push(format!("file={}", escape_property(file)));
+            properties.push(format!("line={line}"));
+            if let Some(col) = col {
+                properties.push(format!("col={col}"));
+            }
+        }
+        properties.push(format!("title={}", escape_property(desc.name.as_slice())));
+
+        let message = match panic {
+            Some(PanicInfo { message, .. }) if !message.is_empty() => message,
+            _ => reason,
+        };
+        self.write_plain(format!("::error {}::{}\n", properties.join(","), escape_data(message)))?;
+
+        if !stdout.is_empty() {
+            self.write_plain(format!("::group::{} output\n", escape_data(desc.name.as_slice())))?;
+            self.write_plain(&*stdout)?;
+            if !stdout.ends_with('\n') {
+                self.write_plain("\n")?;
+            }
+            self.write_plain("::endgroup::\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// The first panic in the captured output of a test.
+#[derive(Clone, Copy)]
+struct PanicInfo<'a> {
+    file: &'a str,
+    line: usize,
+    col: usize,
+    message: &'a str,
+}
+
+/// Finds the first panic in `output`, as printed by the default panic hook:
+/// `thread '<name>' panicked at <file>:<line>:<col>:`, followed by the panic message.
+fn find_panic(output: &str) -> Option<PanicInfo<'_>> {
+    let start = output.find("' panicked at ")? + "' panicked at ".len();
+    let rest = &output[start..];
+    let (location, message) = rest.split_once('\n').unwrap_or((rest, ""));
+    let location = location.strip_suffix(':').unwrap_or(location);
+    // The file name itself may contain `:`, so split the location from the end.
+    let mut parts = location.rsplitn(3, ':');
+    let col = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    let file = parts.next()?;
+
+    // The message ends where the panic hook prints its notes, or at the next stream delimiter.
+    let end = ["\nnote: ", "\nstack backtrace:", "\n---- "]
+        .iter()
+        .filter_map(|delimiter| message.find(delimiter))
+        .min()
+        .unwrap_or(message.len());
+    Some(PanicInfo { file, line, col, message: message[..end].trim_end() })
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+impl<T: Write> OutputFormatter for GithubFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        let noun = if test_count != 1 { "tests" } else { "test" };
+        let shuffle_seed_msg = if let Some(shuffle_seed) = shuffle_seed {
+            format!(" (shuffle seed: {shuffle_seed})")
+        } else {
+            String::new()
+        };
+        self.write_plain(format!("\nrunning {test_count} {noun}{shuffle_seed_msg}\n"))
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Tests are only written once the result is known.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_plain(format!(
+            "::warning title={}::test has been running for over {} seconds\n",
+            escape_property(desc.name.as_slice()),
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        _exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        let name = &desc.name;
+        match *result {
+            TestResult::TrOk => self.write_plain(format!("test {name} ... ok\n")),
+            TestResult::TrFailed => {
+                self.write_plain(format!("test {name} ... FAILED\n"))?;
+                self.write_error(desc, "test failed", stdout)
+            }
+            TestResult::TrFailedMsg(ref m) => {
+                self.write_plain(format!("test {name} ... FAILED\n"))?;
+                self.write_error(desc, m, stdout)
+            }
+            TestResult::TrTimedFail => {
+                self.write_plain(format!("test {name} ... FAILED (time limit exceeded)\n"))?;
+                self.write_error(desc, "time limit exceeded", stdout)
+            }
+            TestResult::TrFlaky(retries) => {
+                let noun = retries_noun(retries);
+                self.write_plain(format!(
+                    "test {name} ... flaky (passed after {retries} {noun})\n"
+                ))?;
+                // Flaky tests fail the test run, unless they are allowed.
+                let command = if state.allow_flaky { "warning" } else { "error" };
+                self.write_plain(format!(
+                    "::{command} title={}::test passed after {retries} {noun}\n",
+                    escape_property(name.as_slice())
+                ))
+            }
+            TestResult::TrIgnored => match desc.ignore_message {
+                Some(message) => self.write_plain(format!("test {name} ... ignored, {message}\n")),
+                None => self.write_plain(format!("test {name} ... ignored\n")),
+            },
+            TestResult::TrBench(ref bs) => {
+                self.write_plain(format!("test {name} ... bench: {}\n", fmt_bench_samples(bs)))
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let success = state.is_success();
+        let mut summary = format!(
+            "\ntest result: {}. {}",
+            if success { "ok" } else { "FAILED" },
+            state.summary_counts()
+        );
+        if let Some(ref exec_time) = state.exec_time {
+            summary.push_str(&format!("; finished in {exec_time}"));
+        }
+        summary.push_str("\n\n");
+        self.write_plain(summary)?;
+
+        Ok(success)
+    }
+}
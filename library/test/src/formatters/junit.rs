@@ -146,6 +146,26 @@ impl<T: Write> OutputFormatter for JunitFormatter<T> {
                     self.write_message("</testcase>")?;
                 }
 
+                TestResult::TrFlaky(retries) => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    // One element per failed run, like the reruns of Maven Surefire.
+                    for _ in 0..retries {
+                        self.write_message("<flakyFailure type=\"assert\"/>")?;
+                    }
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrBench(ref b) => {
                     self.write_message(&format!(
                         "<testcase classname=\"benchmark::{}\" \
@@ -180,7 +200,7 @@ impl<T: Write> OutputFormatter for JunitFormatter<T> {
 
         self.out.write_all(b"\n")?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
@@ -6,14 +6,18 @@ use crate::test_result::TestResult;
 use crate::time;
 use crate::types::{TestDesc, TestName};
 
+mod github;
 mod json;
 mod junit;
 mod pretty;
+mod tap;
 mod terse;
 
+pub(crate) use self::github::GithubFormatter;
 pub(crate) use self::json::JsonFormatter;
 pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::pretty::PrettyFormatter;
+pub(crate) use self::tap::TapFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
@@ -35,6 +39,11 @@ pub(crate) trait OutputFormatter {
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
 }
 
+/// The noun for the retries of a flaky test, e.g. "passed after 2 retries".
+pub(crate) fn retries_noun(retries: usize) -> &'static str {
+    if retries != 1 { "retries" } else { "retry" }
+}
+
 pub(crate) fn write_stderr_delimiter(test_output: &mut Vec<u8>, test_name: &TestName) {
     match test_output.last() {
         Some(b'\n') => (),
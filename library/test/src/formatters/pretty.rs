@@ -1,7 +1,7 @@
 use std::io;
 use std::io::prelude::Write;
 
-use super::OutputFormatter;
+use super::{OutputFormatter, retries_noun};
 use crate::bench::fmt_bench_samples;
 use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
 use crate::test_result::TestResult;
@@ -51,6 +51,14 @@ impl<T: Write> PrettyFormatter<T> {
         }
     }
 
+    pub(crate) fn write_flaky(&mut self, retries: usize) -> io::Result<()> {
+        let noun = retries_noun(retries);
+        self.write_short_result(
+            &format!("flaky (passed after {retries} {noun})"),
+            term::color::YELLOW,
+        )
+    }
+
     pub(crate) fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
@@ -166,6 +174,10 @@ impl<T: Write> PrettyFormatter<T> {
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub(crate) fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_results(&state.flaky_tests, "flaky tests (passed after failing)")
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -249,6 +261,7 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
                 self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrFlaky(retries) => self.write_flaky(retries)?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -267,8 +280,11 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
+        let success = state.is_success();
+        if state.failed != 0 {
             if !state.failures.is_empty() {
                 self.write_failures(state)?;
             }
@@ -287,12 +303,7 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
-        let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
-        );
-
-        self.write_plain(s)?;
+        self.write_plain(format!(". {}", state.summary_counts()))?;
 
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
@@ -0,0 +1,191 @@
+use std::io;
+use std::io::prelude::Write;
+
+use super::json::EscapedString;
+use super::{OutputFormatter, retries_noun};
+use crate::bench::fmt_bench_samples;
+use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::test_result::TestResult;
+use crate::time;
+use crate::types::TestDesc;
+
+/// Formatter for the Test Anything Protocol, version 14 (https://testanything.org/tap-version-14-specification.html).
+///
+/// Every test is a test point, and failures come with a YAML diagnostic block holding the
+/// failure message and the captured output.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    /// Number of the last test point that was written
+    test_number: usize,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub(crate) fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0 }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_plain<S: AsRef<str>>(&mut self, s: S) -> io::Result<()> {
+        let s = s.as_ref();
+        self.out.write_all(s.as_bytes())?;
+        self.out.flush()
+    }
+
+    /// Writes a test point, followed by `comment` if any, which may be a directive like `SKIP`.
+    fn write_test_point(
+        &mut self,
+        ok: bool,
+        desc: &TestDesc,
+        comment: Option<String>,
+    ) -> io::Result<()> {
+        self.test_number += 1;
+        let status = if ok { "ok" } else { "not ok" };
+        // `#` starts a directive, so it has to be escaped in the description.
+        let description = desc.name.as_slice().replace('\\', "\\\\").replace('#', "\\#");
+        let comment = comment.map(|comment| format!(" # {comment}")).unwrap_or_default();
+        self.write_plain(format!("{status} {} - {description}{comment}\n", self.test_number))
+    }
+
+    /// Writes the YAML diagnostic block of the last test point.
+    fn write_diagnostics(
+        &mut self,
+        message: Option<&str>,
+        severity: &str,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        let mut yaml = String::from("  ---\n");
+        if let Some(message) = message {
+            yaml.push_str(&format!("  message: \"{}\"\n", EscapedString(message)));
+        }
+        yaml.push_str(&format!("  severity: {severity}\n"));
+        if let Some(exec_time) = exec_time {
+            yaml.push_str(&format!("  duration_ms: {}\n", exec_time.0.as_secs_f64() * 1000.0));
+        }
+        if !stdout.is_empty() {
+            yaml.push_str(&yaml_block("output", &String::from_utf8_lossy(stdout)));
+        }
+        yaml.push_str("  ...\n");
+        self.write_plain(yaml)
+    }
+}
+
+/// Formats `value` as the YAML field `key`, indented like the diagnostics of a test point.
+fn yaml_block(key: &str, value: &str) -> String {
+    // Literal blocks are the most readable, but cannot hold control characters.
+    if value.chars().any(|c| c.is_control() && c != '\n' && c != '\t') {
+        return format!("  {key}: \"{}\"\n", EscapedString(value));
+    }
+    // The explicit indentation indicator allows the first line to start with spaces, and `-`
+    // drops the trailing newline.
+    let mut block = format!("  {key}: |2-\n");
+    for line in value.lines() {
+        block.push_str(&format!("    {line}\n"));
+    }
+    block
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.write_plain(format!("TAP version 14\n1..{test_count}\n"))?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.write_plain(format!("# shuffle seed: {shuffle_seed}\n"))?;
+        }
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Test points are only written once the result is known.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_plain(format!(
+            "# {} has been running for over {} seconds\n",
+            desc.name,
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        match *result {
+            TestResult::TrOk => {
+                self.write_test_point(true, desc, None)?;
+                if state.options.display_output && !stdout.is_empty() {
+                    self.write_diagnostics(None, "none", exec_time, stdout)?;
+                }
+                Ok(())
+            }
+            TestResult::TrFailed => {
+                self.write_test_point(false, desc, None)?;
+                self.write_diagnostics(Some("test failed"), "fail", exec_time, stdout)
+            }
+            TestResult::TrFailedMsg(ref m) => {
+                self.write_test_point(false, desc, None)?;
+                self.write_diagnostics(Some(m), "fail", exec_time, stdout)
+            }
+            TestResult::TrTimedFail => {
+                self.write_test_point(false, desc, None)?;
+                self.write_diagnostics(Some("time limit exceeded"), "fail", exec_time, stdout)
+            }
+            TestResult::TrFlaky(retries) => {
+                let noun = retries_noun(retries);
+                let comment = format!("flaky, passed after {retries} {noun}");
+                // Flaky tests fail the test run, unless they are allowed.
+                self.write_test_point(state.allow_flaky, desc, Some(comment))?;
+                let severity = if state.allow_flaky { "warn" } else { "fail" };
+                self.write_diagnostics(
+                    Some("test failed before passing"),
+                    severity,
+                    exec_time,
+                    stdout,
+                )
+            }
+            TestResult::TrIgnored => {
+                let directive = match desc.ignore_message {
+                    Some(message) => format!("SKIP {message}"),
+                    None => String::from("SKIP"),
+                };
+                self.write_test_point(true, desc, Some(directive))
+            }
+            TestResult::TrBench(ref bs) => {
+                self.write_test_point(true, desc, None)?;
+                self.write_plain(format!("# bench: {}\n", fmt_bench_samples(bs)))
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let mut summary = format!("# {}", state.summary_counts());
+        if let Some(ref exec_time) = state.exec_time {
+            summary.push_str(&format!("; finished in {exec_time}"));
+        }
+        summary.push('\n');
+        self.write_plain(summary)?;
+
+        Ok(state.is_success())
+    }
+}
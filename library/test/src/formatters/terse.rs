@@ -62,6 +62,10 @@ impl<T: Write> TerseFormatter<T> {
         self.write_plain("\n")
     }
 
+    pub(crate) fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("f", term::color::YELLOW)
+    }
+
     pub(crate) fn write_ignored(&mut self) -> io::Result<()> {
         self.write_short_result("i", term::color::YELLOW)
     }
@@ -172,6 +176,17 @@ impl<T: Write> TerseFormatter<T> {
         Ok(())
     }
 
+    pub(crate) fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests (passed after failing):\n")?;
+        let mut names =
+            state.flaky_tests.iter().map(|(f, _)| f.name.to_string()).collect::<Vec<_>>();
+        names.sort();
+        for name in &names {
+            self.write_plain(format!("    {name}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -234,6 +249,7 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
                 self.write_failed(desc.name.as_slice())
             }
             TestResult::TrIgnored => self.write_ignored(),
+            TestResult::TrFlaky(_) => self.write_flaky(),
             TestResult::TrBench(ref bs) => {
                 if self.is_multithreaded {
                     self.write_test_name(desc)?;
@@ -256,10 +272,13 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        let success = state.is_success();
+        if state.failed != 0 {
             self.write_failures(state)?;
         }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
 
         self.write_plain("\ntest result: ")?;
 
@@ -270,12 +289,7 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
-        let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
-        );
-
-        self.write_plain(s)?;
+        self.write_plain(format!(". {}", state.summary_counts()))?;
 
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
@@ -3,4 +3,5 @@
 
 pub(crate) mod concurrency;
 pub(crate) mod metrics;
+pub(crate) mod shard;
 pub(crate) mod shuffle;
@@ -0,0 +1,13 @@
+use super::shuffle::calculate_hash;
+use crate::options::TestShard;
+use crate::types::TestDescAndFn;
+
+/// Whether `test` belongs to `shard`.
+///
+/// Tests are assigned to shards by the hash of their name rather than by their position,
+/// so that the split is stable across runs, and adding or removing a test does not move
+/// the other tests to other shards.
+pub(crate) fn is_in_shard(shard: TestShard, test: &TestDescAndFn) -> bool {
+    let name_hash = calculate_hash(&test.desc.name.as_slice());
+    name_hash % shard.count as u64 == (shard.index - 1) as u64
+}
@@ -60,7 +60,7 @@ impl Rng {
 }
 
 // `calculate_hash` is from `core/src/hash/mod.rs`.
-fn calculate_hash<T: core::hash::Hash>(t: &T) -> u64 {
+pub(crate) fn calculate_hash<T: core::hash::Hash>(t: &T) -> u64 {
     let mut s = DefaultHasher::new();
     t.hash(&mut s);
     s.finish()
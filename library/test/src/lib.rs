@@ -35,7 +35,7 @@ pub use cli::TestOpts;
 pub use self::ColorConfig::*;
 pub use self::bench::{Bencher, black_box};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic, TestShard};
 pub use self::types::TestName::*;
 pub use self::types::*;
 
@@ -319,6 +319,48 @@ where
         timeout: Instant,
     }
 
+    struct RetryState {
+        testfn: TestFn,
+        failed_attempts: usize,
+        /// Output of the last failed attempt
+        failed_stdout: Vec<u8>,
+    }
+
+    type RetryMap = HashMap<TestId, RetryState, BuildHasherDefault<DefaultHasher>>;
+
+    // With `--retries`, schedules a failed test to run again instead of reporting it, and reports
+    // a test that passes after failing as flaky, with the output of its last failed attempt.
+    // Returns the result to report, if any.
+    fn retry_failed_test(
+        opts: &TestOpts,
+        retry_states: &mut RetryMap,
+        remaining: &mut VecDeque<(TestId, TestDescAndFn)>,
+        mut completed_test: CompletedTest,
+    ) -> Option<CompletedTest> {
+        let Some(state) = retry_states.get_mut(&completed_test.id) else {
+            return Some(completed_test);
+        };
+        match completed_test.result {
+            TrFailed | TrFailedMsg(_) | TrTimedFail if state.failed_attempts < opts.retries => {
+                state.failed_attempts += 1;
+                state.failed_stdout = completed_test.stdout;
+                if let TrFailedMsg(msg) = completed_test.result {
+                    state.failed_stdout.extend_from_slice(format!("note: {msg}").as_bytes());
+                }
+                let testfn = state.testfn.copy_static().unwrap();
+                let test = TestDescAndFn { desc: completed_test.desc, testfn };
+                remaining.push_front((completed_test.id, test));
+                None
+            }
+            TrOk if state.failed_attempts > 0 => {
+                completed_test.result = TrFlaky(state.failed_attempts);
+                completed_test.stdout = std::mem::take(&mut state.failed_stdout);
+                Some(completed_test)
+            }
+            _ => Some(completed_test),
+        }
+    }
+
     let tests_len = tests.len();
 
     let mut filtered = FilteredTests { tests: Vec::new(), benches: Vec::new(), next_id: 0 };
@@ -362,6 +404,20 @@ where
     let mut remaining = VecDeque::from(remaining);
     let mut pending = 0;
 
+    let mut retry_states: RetryMap = HashMap::default();
+    if opts.retries > 0 {
+        // Only static test functions can be run again.
+        for (id, test) in &remaining {
+            if let Some(testfn) = test.testfn.copy_static() {
+                let state = RetryState { testfn, failed_attempts: 0, failed_stdout: Vec::new() };
+                retry_states.insert(*id, state);
+            }
+        }
+    }
+    let is_retry = |retry_states: &RetryMap, id| {
+        retry_states.get(&id).is_some_and(|state: &RetryState| state.failed_attempts > 0)
+    };
+
     let (tx, rx) = channel::<CompletedTest>();
     let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
         RunStrategy::SpawnPrimary
@@ -375,7 +431,7 @@ where
     fn get_timed_out_tests(
         running_tests: &TestMap,
         timeout_queue: &mut VecDeque<TimeoutEntry>,
-    ) -> Vec<TestDesc> {
+    ) -> Vec<TimeoutEntry> {
         let now = Instant::now();
         let mut timed_out = Vec::new();
         while let Some(timeout_entry) = timeout_queue.front() {
@@ -384,32 +440,67 @@ where
             }
             let timeout_entry = timeout_queue.pop_front().unwrap();
             if running_tests.contains_key(&timeout_entry.id) {
-                timed_out.push(timeout_entry.desc);
+                timed_out.push(timeout_entry);
             }
         }
         timed_out
     }
 
-    fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
-        timeout_queue.front().map(|&TimeoutEntry { timeout: next_timeout, .. }| {
+    fn calc_timeout(timeout_queues: &[&VecDeque<TimeoutEntry>]) -> Option<Duration> {
+        let next_timeout = timeout_queues
+            .iter()
+            .filter_map(|queue| queue.front())
+            .map(|entry| entry.timeout)
+            .min();
+        next_timeout.map(|next_timeout| {
             let now = Instant::now();
             if next_timeout >= now { next_timeout - now } else { Duration::new(0, 0) }
         })
     }
 
+    // Tests run in a child process are killed on `--test-timeout`, and report their own result.
+    // Threads running in-process tests cannot be stopped, so those tests are reported as timed out
+    // here and abandoned, and no further tests are started.
+    let in_process_timeout =
+        opts.test_timeout.filter(|_| matches!(run_strategy, RunStrategy::InProcess));
+    let mut hard_timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut abandoned = false;
+
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            let desc = test.desc.clone();
+            if !is_retry(&retry_states, id) {
+                let event = TestEvent::TeWait(desc.clone());
+                notify_about_test_event(event)?;
+            }
             let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
             // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
+            let mut completed_test = match in_process_timeout {
+                Some(timeout) => match rx.recv_timeout(timeout) {
+                    Ok(completed_test) => completed_test,
+                    Err(RecvTimeoutError::Timeout) => {
+                        let event = TestEvent::TeResult(abandon_timed_out_test(id, desc, timeout));
+                        notify_about_test_event(event)?;
+                        // Prevent the abandoned test thread from panicking
+                        std::mem::forget(rx);
+                        return Ok(());
+                    }
+                    Err(RecvTimeoutError::Disconnected) => unreachable!(),
+                },
+                None => rx.recv().unwrap(),
+            };
             RunningTest { join_handle }.join(&mut completed_test);
+            let Some(completed_test) =
+                retry_failed_test(opts, &mut retry_states, &mut remaining, completed_test)
+            else {
+                continue;
+            };
 
             let fail_fast = match completed_test.result {
                 TrIgnored | TrOk | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                TrFlaky(_) => opts.fail_fast && !opts.allow_flaky,
             };
 
             let event = TestEvent::TeResult(completed_test);
@@ -420,32 +511,58 @@ where
             }
         }
     } else {
-        while pending > 0 || !remaining.is_empty() {
-            while pending < concurrency && !remaining.is_empty() {
+        while pending > 0 || (!remaining.is_empty() && !abandoned) {
+            while pending < concurrency && !remaining.is_empty() && !abandoned {
                 let (id, test) = remaining.pop_front().unwrap();
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                if !is_retry(&retry_states, id) {
+                    let event = TestEvent::TeWait(desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
                 let join_handle =
                     run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
                 running_tests.insert(id, RunningTest { join_handle });
+                if let Some(timeout) = in_process_timeout {
+                    let desc = desc.clone();
+                    hard_timeout_queue.push_back(TimeoutEntry {
+                        id,
+                        desc,
+                        timeout: Instant::now() + timeout,
+                    });
+                }
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
             let mut res;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
+                if let Some(timeout) = calc_timeout(&[&timeout_queue, &hard_timeout_queue]) {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
-                        let event = TestEvent::TeTimeout(test);
+                        let event = TestEvent::TeTimeout(test.desc);
                         notify_about_test_event(event)?;
                     }
 
+                    // Only look for tests over `--test-timeout` when no result was received, so
+                    // that the received result is never one of a test that was just abandoned.
+                    if let (Err(RecvTimeoutError::Timeout), Some(timeout)) =
+                        (&res, in_process_timeout)
+                    {
+                        for test in get_timed_out_tests(&running_tests, &mut hard_timeout_queue) {
+                            running_tests.remove(&test.id);
+                            pending -= 1;
+                            abandoned = true;
+                            let event = TestEvent::TeResult(abandon_timed_out_test(
+                                test.id, test.desc, timeout,
+                            ));
+                            notify_about_test_event(event)?;
+                        }
+                    }
+
                     match res {
-                        Err(RecvTimeoutError::Timeout) => {
+                        Err(RecvTimeoutError::Timeout) if pending > 0 => {
                             // Result is not yet ready, continue waiting.
                         }
                         _ => {
@@ -459,13 +576,32 @@ where
                 }
             }
 
-            let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
+            let mut completed_test = match res {
+                Ok(completed_test) => completed_test,
+                // All the running tests were abandoned.
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => unreachable!(),
+            };
+            let Some(running_test) = running_tests.remove(&completed_test.id) else {
+                // The test finished after it was abandoned, and was already reported.
+                continue;
+            };
             running_test.join(&mut completed_test);
+            let id = completed_test.id;
+            let Some(completed_test) =
+                retry_failed_test(opts, &mut retry_states, &mut remaining, completed_test)
+            else {
+                // The timeouts of the failed attempt don't apply to the next one.
+                timeout_queue.retain(|entry| entry.id != id);
+                hard_timeout_queue.retain(|entry| entry.id != id);
+                pending -= 1;
+                continue;
+            };
 
             let fail_fast = match completed_test.result {
                 TrIgnored | TrOk | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                TrFlaky(_) => opts.fail_fast && !opts.allow_flaky,
             };
 
             let event = TestEvent::TeResult(completed_test);
@@ -478,6 +614,12 @@ where
                 return Ok(());
             }
         }
+
+        if abandoned {
+            // Prevent the abandoned test threads from panicking
+            std::mem::forget(rx);
+            return Ok(());
+        }
     }
 
     if opts.bench_benchmarks {
@@ -497,6 +639,16 @@ where
     Ok(())
 }
 
+/// Result of a test run in-process that was still running after `--test-timeout`.
+fn abandon_timed_out_test(id: TestId, desc: TestDesc, timeout: Duration) -> CompletedTest {
+    let message = format!(
+        "test did not finish within the --test-timeout of {}s; it cannot be stopped in-process, \
+         so it was abandoned and no further tests were started\n",
+        timeout.as_secs()
+    );
+    CompletedTest::new(id, desc, TrTimedFail, None, message.into_bytes())
+}
+
 pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
     let mut filtered = tests;
     let matches_filter = |test: &TestDescAndFn, filter: &str| {
@@ -535,6 +687,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Keep only the tests of the requested shard
+    if let Some(shard) = opts.shard {
+        filtered.retain(|test| helpers::shard::is_in_shard(shard, test));
+    }
+
     filtered
 }
 
@@ -586,6 +743,7 @@ pub fn run_test(
             let name = desc.name.clone();
             let nocapture = opts.nocapture;
             let time_options = opts.time_options;
+            let test_timeout = opts.test_timeout;
             let bench_benchmarks = opts.bench_benchmarks;
 
             let runtest = move || match strategy {
@@ -602,9 +760,8 @@ pub fn run_test(
                     id,
                     desc,
                     nocapture,
-                    time_options.is_some(),
                     monitor_ch,
-                    time_options,
+                    SubprocessTiming { time_opts: time_options, test_timeout },
                     bench_benchmarks,
                 ),
             };
@@ -699,15 +856,24 @@ where
     }
 }
 
+/// How the execution time of a test run in a subprocess is reported and limited.
+#[derive(Clone, Copy)]
+struct SubprocessTiming {
+    /// Thresholds for the reported execution time, if it is reported at all.
+    time_opts: Option<time::TestTimeOptions>,
+    /// Time after which the subprocess is killed.
+    test_timeout: Option<Duration>,
+}
+
 fn spawn_test_subprocess(
     id: TestId,
     desc: TestDesc,
     nocapture: bool,
-    report_time: bool,
     monitor_ch: Sender<CompletedTest>,
-    time_opts: Option<time::TestTimeOptions>,
+    timing: SubprocessTiming,
     bench_benchmarks: bool,
 ) {
+    let SubprocessTiming { time_opts, test_timeout } = timing;
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
         let current_exe = &args[0];
@@ -722,8 +888,12 @@ fn spawn_test_subprocess(
             command.stderr(process::Stdio::inherit());
         }
 
-        let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let start = time_opts.is_some().then(Instant::now);
+        let output = match test_timeout {
+            Some(timeout) => output_with_timeout(command, nocapture, timeout),
+            None => command.output().map(|output| (output, false)),
+        };
+        let output = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -735,11 +905,20 @@ fn spawn_test_subprocess(
             TestExecTime(duration)
         });
 
-        let std::process::Output { stdout, stderr, status } = output;
+        let (std::process::Output { stdout, stderr, status }, timed_out) = output;
         let mut test_output = stdout;
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            let timeout = test_timeout.unwrap().as_secs();
+            let err = format!(
+                "test did not finish within the --test-timeout of {timeout}s and was killed\n"
+            );
+            test_output.extend_from_slice(err.as_bytes());
+            return (TrTimedFail, test_output, exec_time);
+        }
+
         let result =
             get_result_from_exit_code(&desc, status, time_opts.as_ref(), exec_time.as_ref());
         (result, test_output, exec_time)
@@ -749,6 +928,53 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Runs `command` like `Command::output`, but kills the child process if it is still running
+/// after `timeout`. Also returns whether the child process was killed.
+fn output_with_timeout(
+    mut command: Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    fn read_in_thread(
+        pipe: Option<impl io::Read + Send + 'static>,
+    ) -> Option<thread::JoinHandle<Vec<u8>>> {
+        pipe.map(|mut pipe| {
+            thread::spawn(move || {
+                let mut buf = Vec::new();
+                let _ = pipe.read_to_end(&mut buf);
+                buf
+            })
+        })
+    }
+
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn()?;
+    // Read the pipes while waiting, so that the child doesn't block on a full pipe.
+    let stdout = read_in_thread(child.stdout.take());
+    let stderr = read_in_thread(child.stderr.take());
+
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let join = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader.map(|reader| reader.join().unwrap_or_default()).unwrap_or_default()
+    };
+    let output = process::Output { status, stdout: join(stdout), stderr: join(stderr) };
+    Ok((output, timed_out))
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicHookInfo<'_>>| {
@@ -36,6 +36,10 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP (Test Anything Protocol) version 14 output
+    Tap,
+    /// Output annotating failures in GitHub Actions
+    Github,
 }
 
 /// Whether ignored test should be run or not
@@ -47,6 +51,16 @@ pub enum RunIgnored {
     Only,
 }
 
+/// Subset of the tests to run, when the tests are split into several shards,
+/// e.g. to run them on several machines
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// Index of the shard to run, from 1 to `count`
+    pub index: usize,
+    /// Number of shards that the tests are split into
+    pub count: usize,
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
@@ -35,6 +35,8 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test passed after failing, and being retried the given number of times.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
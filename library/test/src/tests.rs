@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{GithubFormatter, OutputFormatter, PrettyFormatter, TapFormatter},
     test::{
         MetricMap,
         // FIXME (introduced by #65251)
@@ -29,9 +29,13 @@ impl TestOpts {
             format: OutputFormat::Pretty,
             shuffle: false,
             shuffle_seed: None,
+            shard: None,
             test_threads: None,
             skip: vec![],
             time_options: None,
+            test_timeout: None,
+            retries: 0,
+            allow_flaky: false,
             options: Options::new(),
             fail_fast: false,
         }
@@ -721,6 +725,72 @@ fn order_depends_on_more_than_seed() {
     assert!(left.iter().zip(right).any(|(a, b)| a.0 != b.0));
 }
 
+#[test]
+fn parse_shard_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard".to_string(),
+        "2/3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(TestShard { index: 2, count: 3 }));
+}
+
+#[test]
+fn parse_invalid_shard_option() {
+    for shard in ["0/3", "4/3", "1/0", "1", "a/b"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--shard".to_string(),
+            shard.to_string(),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err(), "--shard {shard} should be rejected");
+    }
+}
+
+#[test]
+fn shards_partition_tests() {
+    let count = 3;
+    let mut sharded_names = Vec::new();
+    for index in 1..=count {
+        let opts = TestOpts { shard: Some(TestShard { index, count }), ..TestOpts::new() };
+        let shard = filter_tests(&opts, sample_tests());
+        // The split does not depend on the run
+        let again = filter_tests(&opts, sample_tests());
+        assert!(shard.iter().zip(&again).all(|(a, b)| a.desc.name == b.desc.name));
+        assert_eq!(shard.len(), again.len());
+        sharded_names.extend(shard.into_iter().map(|test| test.desc.name.to_string()));
+    }
+
+    // Every test runs in exactly one shard
+    sharded_names.sort();
+    let mut names =
+        sample_tests().into_iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(sharded_names, names);
+}
+
+#[test]
+fn shard_does_not_depend_on_other_tests() {
+    let opts = TestOpts { shard: Some(TestShard { index: 1, count: 2 }), ..TestOpts::new() };
+    let mut tests = sample_tests();
+    let removed = tests.remove(0).desc.name.to_string();
+
+    let all = filter_tests(&opts, sample_tests());
+    let without_removed = filter_tests(&opts, tests);
+
+    let names = all
+        .into_iter()
+        .map(|test| test.desc.name.to_string())
+        .filter(|name| *name != removed)
+        .collect::<Vec<_>>();
+    let names_without_removed =
+        without_removed.into_iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>();
+    assert_eq!(names, names_without_removed);
+}
+
 #[test]
 fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -855,6 +925,7 @@ fn should_sort_failures_before_printing_them() {
         total: 0,
         passed: 0,
         failed: 0,
+        flaky: 0,
         ignored: 0,
         filtered_out: 0,
         measured: 0,
@@ -865,6 +936,8 @@ fn should_sort_failures_before_printing_them() {
         not_failures: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        allow_flaky: false,
     };
 
     out.write_failures(&st).unwrap();
@@ -912,3 +985,268 @@ fn test_dyn_bench_returning_err_fails_when_run_as_test() {
     let result = rx.recv().unwrap().result;
     assert_eq!(result, TrFailed);
 }
+
+#[test]
+fn parse_test_timeout_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "0".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+fn hanging_and_quick_tests() -> Vec<TestDescAndFn> {
+    fn hangs() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(10));
+        Ok(())
+    }
+    fn quick() -> Result<(), String> {
+        Ok(())
+    }
+    let test = |name, f: fn() -> Result<(), String>| TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName(name),
+            ignore: false,
+            ignore_message: None,
+            source_file: "",
+            start_line: 0,
+            start_col: 0,
+            end_line: 0,
+            end_col: 0,
+            should_panic: ShouldPanic::No,
+            compile_fail: false,
+            no_run: false,
+            test_type: TestType::Unknown,
+        },
+        testfn: DynTestFn(Box::new(f)),
+    };
+    vec![test("hangs", hangs), test("quick", quick)]
+}
+
+fn run_with_test_timeout(test_threads: usize) -> Vec<(String, TestResult)> {
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        test_timeout: Some(Duration::from_millis(200)),
+        ..TestOpts::new()
+    };
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push((completed_test.desc.name.to_string(), completed_test.result));
+        }
+        Ok(())
+    };
+    run_tests(&opts, hanging_and_quick_tests(), notify).unwrap();
+    results
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_timeout_abandons_in_process_test() {
+    // The hanging test is abandoned, and the next test is not started.
+    let results = run_with_test_timeout(1);
+    assert_eq!(results, vec![("hangs".to_string(), TrTimedFail)]);
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_timeout_waits_for_other_running_tests() {
+    let mut results = run_with_test_timeout(2);
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(results, vec![("hangs".to_string(), TrTimedFail), ("quick".to_string(), TrOk)]);
+}
+
+fn formatter_test_desc(name: &'static str) -> TestDesc {
+    TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        source_file: "src/lib.rs",
+        start_line: 3,
+        start_col: 4,
+        end_line: 3,
+        end_col: 10,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+    }
+}
+
+const PANIC_OUTPUT: &str = "\nthread 'b' panicked at src/b.rs:10:5:\n\
+                            assertion failed: false\n\
+                            note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+
+#[test]
+fn tap_formatter_output() {
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    let mut ignored = formatter_test_desc("c");
+    ignored.ignore_message = Some("slow");
+
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&formatter_test_desc("a#1"), &TrOk, None, b"", &st).unwrap();
+    out.write_result(&formatter_test_desc("b"), &TrFailed, None, PANIC_OUTPUT.as_bytes(), &st)
+        .unwrap();
+    out.write_result(&ignored, &TrIgnored, None, b"", &st).unwrap();
+    st.passed = 1;
+    st.failed = 1;
+    st.ignored = 1;
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let expected = "TAP version 14
+1..3
+ok 1 - a\\#1
+not ok 2 - b
+  ---
+  message: \"test failed\"
+  severity: fail
+  output: |2-
+    
+    thread 'b' panicked at src/b.rs:10:5:
+    assertion failed: false
+    note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+  ...
+ok 3 - c # SKIP slow
+# 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+";
+    assert_eq!(s, expected);
+}
+
+#[test]
+fn github_formatter_annotates_failures() {
+    let mut out = GithubFormatter::new(OutputLocation::Raw(Vec::new()));
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    out.write_result(&formatter_test_desc("a"), &TrOk, None, b"", &st).unwrap();
+    out.write_result(&formatter_test_desc("b"), &TrFailed, None, PANIC_OUTPUT.as_bytes(), &st)
+        .unwrap();
+    let message = TrFailedMsg("test did not panic as expected\nat line 3".to_string());
+    out.write_result(&formatter_test_desc("m::c"), &message, None, b"", &st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let lines = s.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "test a ... ok");
+    assert_eq!(lines[1], "test b ... FAILED");
+    // The panic location and message are taken from the captured output.
+    assert_eq!(lines[2], "::error file=src/b.rs,line=10,col=5,title=b::assertion failed: false");
+    assert_eq!(lines[3], "::group::b output");
+    assert_eq!(lines[lines.len() - 3], "::endgroup::");
+    assert_eq!(lines[lines.len() - 2], "test m::c ... FAILED");
+    // Without a panic, the test itself is annotated with the failure message.
+    assert_eq!(
+        lines[lines.len() - 1],
+        "::error file=src/lib.rs,line=3,col=4,title=m%3A%3Ac::test did not panic as expected%0Aat line 3"
+    );
+}
+
+#[test]
+fn parse_retries_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries".to_string(),
+        "2".to_string(),
+        "--allow-flaky".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 2);
+    assert!(opts.allow_flaky);
+}
+
+fn run_with_retries(testfn: fn() -> Result<(), String>, test_threads: usize) -> TestResult {
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            ignore_message: None,
+            source_file: "",
+            start_line: 0,
+            start_col: 0,
+            end_line: 0,
+            end_col: 0,
+            should_panic: ShouldPanic::No,
+            compile_fail: false,
+            no_run: false,
+            test_type: TestType::Unknown,
+        },
+        testfn: StaticTestFn(testfn),
+    };
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        retries: 2,
+        ..TestOpts::new()
+    };
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push(completed_test.result);
+        }
+        Ok(())
+    };
+    run_tests(&opts, vec![desc], notify).unwrap();
+    assert_eq!(results.len(), 1);
+    results.pop().unwrap()
+}
+
+#[test]
+fn test_passing_after_retry_is_flaky() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn f() -> Result<(), String> {
+        if RUNS.fetch_add(1, Ordering::SeqCst) % 2 == 0 { Err("first run".into()) } else { Ok(()) }
+    }
+    assert_eq!(run_with_retries(f, 1), TrFlaky(1));
+    assert_eq!(run_with_retries(f, 2), TrFlaky(1));
+    assert_eq!(RUNS.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_failing_all_retries_fails() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn f() -> Result<(), String> {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        Err("always fails".into())
+    }
+    assert_eq!(run_with_retries(f, 2), TrFailed);
+    // The test is run once, and then retried twice.
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn flaky_tests_fail_unless_allowed() {
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    st.passed = 1;
+    st.flaky = 1;
+    assert!(!st.is_success());
+    assert_eq!(
+        st.summary_counts(),
+        "1 passed; 0 failed; 1 flaky; 0 ignored; 0 measured; 0 filtered out"
+    );
+
+    st.allow_flaky = true;
+    assert!(st.is_success());
+}
@@ -0,0 +1,683 @@
+use anyhow::Result;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use to_markdown_table::MarkdownTable;
+
+use crate::{make_tables::write_table_to_file, missing_arbitrary::split_top_level, OutputFormat};
+
+// `kani list` JSON format version that this module knows how to read.
+const EXPECTED_KANI_LIST_VERSION: &str = "0.1";
+
+// Crate names that may start the module path in the Location column of a challenge.
+const CRATE_NAMES: [&str; 3] = ["core", "alloc", "std"];
+
+// Prefixes of the names of the harnesses in `verify` modules, followed by the name of the function
+// they verify. These are the patterns that kani-std-analysis/log_parser.py recognizes as well.
+const HARNESS_PREFIXES: [&str; 4] = ["non_null_check_", "nonzero_check_", "check_", "verify_"];
+
+/// Output of `kani list --format json`
+#[derive(Debug, Deserialize)]
+struct KaniList {
+    #[serde(rename = "file-version")]
+    file_version: String,
+    /// Map each file to the harnesses that are not attached to a contract.
+    #[serde(rename = "standard-harnesses")]
+    standard_harnesses: BTreeMap<String, Vec<String>>,
+    contracts: Vec<KaniListContract>,
+}
+
+/// A function under contract, and the harnesses that verify its contract.
+#[derive(Debug, Deserialize)]
+struct KaniListContract {
+    function: String,
+    file: String,
+    harnesses: Vec<String>,
+}
+
+/// What restricts a function listed by a challenge to a particular module or file,
+/// beyond its name.
+#[derive(Debug, Clone)]
+enum Location {
+    /// A module path, like `core::intrinsics`.
+    Module(String),
+    /// A file name, like `clone.rs`.
+    File(String),
+}
+
+/// A function that a challenge asks to verify.
+#[derive(Debug)]
+struct ChallengeFn {
+    name: String,
+    location: Option<Location>,
+}
+
+/// How far the verification of a function listed by a challenge got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+enum CoverageStatus {
+    /// At least one harness verifies the function.
+    #[strum(serialize = "Harness")]
+    Harness,
+    /// The function has a contract, but no harness verifies it.
+    #[strum(serialize = "Contract only")]
+    ContractOnly,
+    /// The function has neither a contract nor a harness.
+    #[strum(serialize = "Missing")]
+    Missing,
+}
+
+#[derive(Serialize)]
+struct FunctionCoverage {
+    name: String,
+    /// Absent if the challenge only lists the function by name.
+    location: Option<String>,
+    status: CoverageStatus,
+    /// The functions under contract in `kani list` that match the name.
+    contracts: Vec<String>,
+    /// The harnesses that verify one of the matching functions.
+    harnesses: Vec<String>,
+}
+
+/// The coverage of the functions listed by one challenge.
+#[derive(Serialize)]
+pub struct ChallengeCoverage {
+    /// The file name of the challenge, without extension, like `0001-core-transmutation`.
+    id: String,
+    title: String,
+    functions: Vec<FunctionCoverage>,
+}
+
+/// Remove the generic arguments and the whitespace from `s`.
+fn erase_generics(s: &str) -> String {
+    let mut erased = String::new();
+    let mut depth = 0;
+    let mut prev = ' ';
+    for c in s.chars() {
+        match c {
+            '>' if prev == '-' => erased.push(c),
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            c if depth == 0 && !c.is_whitespace() => erased.push(c),
+            _ => {}
+        }
+        prev = c;
+    }
+    erased
+}
+
+/// The name of a self type: its last path segment without generic arguments,
+/// e.g. `Chars` for `str::iter::Chars<'a>`.
+fn type_name(ty: &str) -> String {
+    let last = split_top_level(ty, "::").last().copied().unwrap_or(ty);
+    erase_generics(last)
+}
+
+/// Normalize a function path so that the paths in `kani list` and the names in the challenges can
+/// be compared segment by segment.
+///
+/// Impl blocks and qualified paths are replaced by the name of their self type, e.g.
+/// `<impl Iterator for Chars<'a>>::next` and `<Chars<'a> as Iterator>::next` both become
+/// `Chars::next`, and generic arguments are erased, e.g. `Option::<T>::as_slice` becomes
+/// `Option::as_slice`.
+pub fn path_segments(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    for (index, segment) in split_top_level(path, "::").into_iter().enumerate() {
+        let Some(inner) = segment
+            .strip_prefix('<')
+            .and_then(|rest| rest.strip_suffix('>'))
+        else {
+            segments.push(erase_generics(segment));
+            continue;
+        };
+        if let Some(impl_ty) = inner.strip_prefix("impl ") {
+            let self_ty = impl_ty.split_once(" for ").map_or(impl_ty, |(_, ty)| ty);
+            segments.push(type_name(self_ty));
+        } else if let Some((self_ty, _)) = inner.split_once(" as ") {
+            segments.push(type_name(self_ty));
+        } else if index == 0 {
+            segments.push(type_name(inner));
+        }
+        // Otherwise the segment is the generic arguments of the previous one
+    }
+    segments
+}
+
+/// Whether `haystack` contains `needle` as a contiguous subsequence.
+fn contains_segments(haystack: &[String], needle: &[String]) -> bool {
+    needle.is_empty()
+        || haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+/// The cells of a Markdown table row.
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|')
+        .map(|cell| cell.replace('`', "").trim().to_string())
+        .collect()
+}
+
+fn is_table_separator(line: &str) -> bool {
+    line.trim().starts_with('|')
+        && table_cells(line)
+            .iter()
+            .all(|cell| cell.chars().all(|c| matches!(c, '-' | ':' | ' ')))
+}
+
+/// The function named by a list item like ``1. `Duration::new(secs: u64, nanos: u32)` ``.
+fn list_item_fn(line: &str) -> Option<String> {
+    let line = line.trim_start();
+    let item = match line.split_once(' ') {
+        Some((marker, item))
+            if marker == "-"
+                || marker == "*"
+                || marker
+                    .strip_suffix('.')
+                    .is_some_and(|number| number.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            item.trim_start()
+        }
+        _ => return None,
+    };
+    let (code, _) = item.strip_prefix('`')?.split_once('`')?;
+    // Remove the signature, if any
+    let name = code.split_once('(').map_or(code, |(name, _)| name).trim();
+    // Macro invocations and other code are not functions
+    let is_path = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || " _:<>[];*&',".contains(c));
+    is_path.then(|| name.to_string())
+}
+
+/// Extract the functions that a challenge asks to verify, from the tables whose first column is
+/// `Function` or `Method`, and from the list items that start with a function name in backticks.
+///
+/// The second column of a table narrows down the function: `Location` is its module, `Defined in`
+/// its file, `Impl for` its self type, and a column of types like `Integer Types` lists the self
+/// types of the function, one function per type.
+fn challenge_fns(text: &str) -> Vec<ChallengeFn> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut fns = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let is_table_header = line.trim().starts_with('|')
+            && lines
+                .get(index + 1)
+                .is_some_and(|next| is_table_separator(next));
+        if !is_table_header {
+            fns.extend(list_item_fn(line).map(|name| ChallengeFn {
+                name,
+                location: None,
+            }));
+            index += 1;
+            continue;
+        }
+
+        let header = table_cells(line);
+        let is_fn_table = header
+            .first()
+            .is_some_and(|column| ["function", "method"].contains(&column.to_lowercase().as_str()));
+        let qualifier = header.get(1).map(|column| column.to_lowercase());
+        index += 2;
+        while index < lines.len() && lines[index].trim().starts_with('|') {
+            let row = table_cells(lines[index]);
+            index += 1;
+            let Some(name) = row.first().filter(|name| is_fn_table && !name.is_empty()) else {
+                continue;
+            };
+            let detail = row.get(1).filter(|detail| !detail.is_empty());
+            match (qualifier.as_deref(), detail) {
+                (Some("location"), Some(module)) => fns.push(ChallengeFn {
+                    name: name.clone(),
+                    location: Some(Location::Module(module.clone())),
+                }),
+                (Some("defined in"), Some(file)) => fns.push(ChallengeFn {
+                    name: name.clone(),
+                    location: Some(Location::File(file.clone())),
+                }),
+                (Some("impl for"), Some(self_ty)) => fns.push(ChallengeFn {
+                    name: format!("{self_ty}::{name}"),
+                    location: None,
+                }),
+                (Some(column), Some(self_tys)) if column.ends_with("types") => {
+                    fns.extend(self_tys.split(',').map(|self_ty| ChallengeFn {
+                        name: format!("{}::{name}", self_ty.trim()),
+                        location: None,
+                    }))
+                }
+                _ => fns.push(ChallengeFn {
+                    name: name.clone(),
+                    location: None,
+                }),
+            }
+        }
+    }
+    fns
+}
+
+/// The source files or directories that a challenge mentions, like `library/core/src/slice/mod.rs`,
+/// without the `library/` prefix.
+fn challenge_files(text: &str) -> BTreeSet<String> {
+    text.match_indices("library/")
+        .map(|(start, _)| {
+            text[start + "library/".len()..]
+                .split(|c: char| !(c.is_alphanumeric() || "_/.".contains(c)))
+                .next()
+                .unwrap_or("")
+                .trim_end_matches('.')
+        })
+        .filter(|path| path.ends_with(".rs") || path.ends_with('/'))
+        .map(String::from)
+        .collect()
+}
+
+/// The contracts and the harnesses that may verify the functions of a challenge.
+struct Candidates<'a> {
+    kani_list: &'a KaniList,
+    /// The source files that the challenge restricts its functions to, if any.
+    files: BTreeSet<String>,
+}
+
+impl Candidates<'_> {
+    fn in_files(&self, file: &str, location: Option<&Location>) -> bool {
+        let in_challenge_files =
+            self.files.is_empty() || self.files.iter().any(|path| file.contains(path.as_str()));
+        let in_location_file = match location {
+            Some(Location::File(name)) => file.ends_with(&format!("/{name}")),
+            _ => true,
+        };
+        in_challenge_files && in_location_file
+    }
+
+    /// The segments of the module that the challenge restricts a function to, if any,
+    /// without the crate name.
+    fn module(location: Option<&Location>) -> Option<Vec<String>> {
+        let Some(Location::Module(module)) = location else {
+            return None;
+        };
+        let mut module = path_segments(module);
+        if module
+            .first()
+            .is_some_and(|krate| CRATE_NAMES.contains(&krate.as_str()))
+        {
+            module.remove(0);
+        }
+        Some(module)
+    }
+
+    /// Find the contracts and harnesses for a function listed by the challenge.
+    ///
+    /// A function under contract matches if its path ends with the name of the function, and
+    /// contains the module of the function, if the challenge gives one.
+    /// Harnesses that are not attached to a contract are matched by the name of the function they
+    /// are named after, like `verify::check_<name>`, since their path does not tell which type
+    /// the function belongs to, so their `verify` module must be directly in the module of the
+    /// function.
+    fn coverage(&self, challenge_fn: ChallengeFn) -> FunctionCoverage {
+        let name = path_segments(&challenge_fn.name);
+        let location = challenge_fn.location.as_ref();
+        let module = Self::module(location);
+
+        let mut contracts = BTreeSet::new();
+        let mut harnesses = BTreeSet::new();
+        for contract in &self.kani_list.contracts {
+            let segments = path_segments(&contract.function);
+            if segments.ends_with(&name)
+                && module
+                    .as_ref()
+                    .is_none_or(|module| contains_segments(&segments, module))
+                && self.in_files(&contract.file, location)
+            {
+                contracts.insert(contract.function.clone());
+                harnesses.extend(contract.harnesses.iter().cloned());
+            }
+        }
+        for (file, file_harnesses) in &self.kani_list.standard_harnesses {
+            if !self.in_files(file, location) {
+                continue;
+            }
+            for harness in file_harnesses {
+                let Some((harness_module, harness_name)) = harness.rsplit_once("::verify::") else {
+                    continue;
+                };
+                let Some(fn_name) = HARNESS_PREFIXES
+                    .iter()
+                    .find_map(|prefix| harness_name.strip_prefix(prefix))
+                else {
+                    continue;
+                };
+                if name.last().is_some_and(|last| last == fn_name)
+                    && module
+                        .as_ref()
+                        .is_none_or(|module| path_segments(harness_module).ends_with(module))
+                {
+                    harnesses.insert(harness.clone());
+                }
+            }
+        }
+
+        let status = if !harnesses.is_empty() {
+            CoverageStatus::Harness
+        } else if !contracts.is_empty() {
+            CoverageStatus::ContractOnly
+        } else {
+            CoverageStatus::Missing
+        };
+        FunctionCoverage {
+            name: challenge_fn.name,
+            location: challenge_fn.location.map(|location| match location {
+                Location::Module(module) => module,
+                Location::File(file) => file,
+            }),
+            status,
+            contracts: contracts.into_iter().collect(),
+            harnesses: harnesses.into_iter().collect(),
+        }
+    }
+}
+
+/// Resolve the functions listed by every challenge in `challenges_dir_path` against the output of
+/// `kani list` in `kani_list_path`, ordered by challenge.
+pub fn read_challenge_coverage(
+    challenges_dir_path: &Path,
+    kani_list_path: &Path,
+) -> Result<Vec<ChallengeCoverage>> {
+    let kani_list_data = fs::read_to_string(kani_list_path)
+        .unwrap_or_else(|_| panic!("Unable to read {kani_list_path:?}"));
+    let kani_list: KaniList = serde_json::from_str(&kani_list_data)?;
+    if kani_list.file_version != EXPECTED_KANI_LIST_VERSION {
+        anyhow::bail!(
+            "kani list JSON file version {} does not match expected version {EXPECTED_KANI_LIST_VERSION}",
+            kani_list.file_version
+        );
+    }
+
+    let mut challenge_paths = fs::read_dir(challenges_dir_path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    challenge_paths.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
+    challenge_paths.sort();
+
+    let mut challenges = Vec::new();
+    for path in challenge_paths {
+        let text = fs::read_to_string(&path)?;
+        let title = text
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let candidates = Candidates {
+            kani_list: &kani_list,
+            files: challenge_files(&text),
+        };
+        challenges.push(ChallengeCoverage {
+            id: path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            title,
+            functions: challenge_fns(&text)
+                .into_iter()
+                .map(|challenge_fn| candidates.coverage(challenge_fn))
+                .collect(),
+        });
+    }
+
+    Ok(challenges)
+}
+
+fn count(challenge: &ChallengeCoverage, status: CoverageStatus) -> usize {
+    challenge
+        .functions
+        .iter()
+        .filter(|function| function.status == status)
+        .count()
+}
+
+fn summary_table(challenges: &[ChallengeCoverage]) -> Result<MarkdownTable> {
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Challenge".to_string(),
+            "# of functions".to_string(),
+            "# with harnesses".to_string(),
+            "# with contracts only".to_string(),
+            "# missing".to_string(),
+        ]),
+        challenges
+            .iter()
+            .map(|challenge| {
+                vec![
+                    challenge.title.clone(),
+                    challenge.functions.len().to_string(),
+                    count(challenge, CoverageStatus::Harness).to_string(),
+                    count(challenge, CoverageStatus::ContractOnly).to_string(),
+                    count(challenge, CoverageStatus::Missing).to_string(),
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+fn functions_table(challenge: &ChallengeCoverage) -> Result<MarkdownTable> {
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Function".to_string(),
+            "Location".to_string(),
+            "Status".to_string(),
+            "Contracts".to_string(),
+            "Harnesses".to_string(),
+        ]),
+        challenge
+            .functions
+            .iter()
+            .map(|function| {
+                vec![
+                    function.name.clone(),
+                    function.location.clone().unwrap_or_default(),
+                    function.status.to_string(),
+                    function.contracts.join(", "),
+                    function.harnesses.join(", "),
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+/// Report which functions of each challenge have contracts, which have harnesses, and which are
+/// missing.
+pub fn compute_challenge_coverage(
+    challenges: &[ChallengeCoverage],
+    format: OutputFormat,
+) -> Result<()> {
+    let out_path = Path::new("challenge_coverage").with_extension(match format {
+        OutputFormat::Markdown => "md",
+        OutputFormat::Json => "json",
+    });
+    let mut out_file = File::create(&out_path)?;
+    match format {
+        OutputFormat::Markdown => {
+            write_table_to_file(&mut out_file, &summary_table(challenges)?)?;
+            for challenge in challenges.iter().filter(|c| !c.functions.is_empty()) {
+                writeln!(out_file, "## {}\n", challenge.title)?;
+                write_table_to_file(&mut out_file, &functions_table(challenge)?)?;
+            }
+        }
+        OutputFormat::Json => serde_json::to_writer_pretty(out_file, challenges)?,
+    }
+    println!("Wrote results to {}", out_path.to_string_lossy());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The names and locations of the functions that `challenge_fns` finds in `text`.
+    fn fns(text: &str) -> Vec<(String, Option<String>)> {
+        challenge_fns(text)
+            .into_iter()
+            .map(|f| {
+                let location = f.location.map(|location| match location {
+                    Location::Module(module) => format!("module {module}"),
+                    Location::File(file) => format!("file {file}"),
+                });
+                (f.name, location)
+            })
+            .collect()
+    }
+
+    fn named(name: &str, location: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), location.map(String::from))
+    }
+
+    #[test]
+    fn location_table() {
+        let text = "\
+| Function              | Location          |
+|-----------------------|-------------------|
+| `transmute_unchecked` | `core::intrinsics` |
+| *const T::add              | core::ptr       |
+| typed_swap |  |
+";
+        assert_eq!(
+            fns(text),
+            [
+                named("transmute_unchecked", Some("module core::intrinsics")),
+                named("*const T::add", Some("module core::ptr")),
+                named("typed_swap", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn defined_in_table() {
+        let text = "\
+| Function | Defined in |
+|---------| ---------|
+|__iterator_get_unchecked| clone.rs|
+|next_unchecked| clone.rs|
+";
+        assert_eq!(
+            fns(text),
+            [
+                named("__iterator_get_unchecked", Some("file clone.rs")),
+                named("next_unchecked", Some("file clone.rs")),
+            ]
+        );
+    }
+
+    #[test]
+    fn impl_for_table() {
+        let text = "\
+| Function | Impl for |
+|---------| ---------|
+|next| Chars|
+|__iterator_get_unchecked| Windows|
+";
+        assert_eq!(
+            fns(text),
+            [
+                named("Chars::next", None),
+                named("Windows::__iterator_get_unchecked", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn types_table() {
+        let text = "\
+| Method              | Integer Types |
+| :---           |     :---
+| `unchecked_add`  |  `i8`, `u128` |
+";
+        assert_eq!(
+            fns(text),
+            [
+                named("i8::unchecked_add", None),
+                named("u128::unchecked_add", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn other_tables_and_text() {
+        let text = "\
+| Trait | Location |
+|-------|----------|
+| Clone | core::clone |
+
+Verify the safety of:
+1. `Duration::new(secs: u64, nanos: u32)`
+- `String::remove`
+* `matches!(x, y)`
+- Plain text
+";
+        assert_eq!(
+            fns(text),
+            [named("Duration::new", None), named("String::remove", None)]
+        );
+    }
+
+    #[test]
+    fn list_items() {
+        assert_eq!(
+            list_item_fn("  12. `<[T]>::as_chunks_unchecked<const N: usize>(&self)`").as_deref(),
+            Some("<[T]>::as_chunks_unchecked<const N: usize>")
+        );
+        assert_eq!(
+            list_item_fn("* `NonNull::<T>::add`").as_deref(),
+            Some("NonNull::<T>::add")
+        );
+        assert_eq!(list_item_fn("- `vec![x; n]`"), None);
+        assert_eq!(list_item_fn("- ``"), None);
+        assert_eq!(list_item_fn("a. `foo`"), None);
+        assert_eq!(list_item_fn("`foo`"), None);
+    }
+
+    #[test]
+    fn generics_are_erased() {
+        assert_eq!(erase_generics("Option<T>"), "Option");
+        assert_eq!(erase_generics("Vec<Box<T>, A>"), "Vec");
+        assert_eq!(erase_generics("fn(T) -> U"), "fn(T)->U");
+    }
+
+    #[test]
+    fn segments_of_paths() {
+        let segments = |path| path_segments(path).join("::");
+        assert_eq!(
+            segments("core::option::Option::<T>::as_slice"),
+            "core::option::Option::as_slice"
+        );
+        assert_eq!(
+            segments("<impl Iterator for Chars<'a>>::next"),
+            "Chars::next"
+        );
+        assert_eq!(segments("<Chars<'a> as Iterator>::next"), "Chars::next");
+        assert_eq!(
+            segments("core::str::<impl core::iter::Iterator for core::str::iter::Chars<'a>>::next"),
+            "core::str::Chars::next"
+        );
+        assert_eq!(
+            segments("<core::str::iter::Chars<'a> as core::iter::Iterator>::next"),
+            "Chars::next"
+        );
+        assert_eq!(segments("<impl Duration>::new"), "Duration::new");
+        assert_eq!(segments("<NonNull<T>>::add"), "NonNull::add");
+        assert_eq!(segments("Vec::<T, A>::push"), "Vec::push");
+    }
+}
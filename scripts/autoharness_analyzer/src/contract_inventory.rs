@@ -0,0 +1,911 @@
+use anyhow::Result;
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use serde::Serialize;
+use strum_macros::Display;
+use syn::{
+    parse::Parser, punctuated::Punctuated, visit::Visit, Attribute, ImplItem, ImplItemFn, Item,
+    ItemImpl, Meta, ReturnType, Token, TraitItem, Type, TypePath, Visibility,
+};
+use to_markdown_table::MarkdownTable;
+
+use crate::{
+    challenges::path_segments, make_tables::write_table_to_file,
+    missing_arbitrary::ARBITRARY_PRIMITIVES, OutputFormat,
+};
+
+// Crates of the standard library whose sources are inventoried.
+const CRATES: [&str; 3] = ["core", "alloc", "std"];
+
+/// An annotation that specifies the behavior of a function or type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+enum ContractKind {
+    #[strum(serialize = "requires")]
+    Requires,
+    #[strum(serialize = "ensures")]
+    Ensures,
+    #[strum(serialize = "modifies")]
+    Modifies,
+    #[strum(serialize = "loop_invariant")]
+    LoopInvariant,
+    #[strum(serialize = "invariant")]
+    Invariant,
+    #[strum(serialize = "derive(Invariant)")]
+    DeriveInvariant,
+}
+
+impl ContractKind {
+    /// Whether the contract is checked by a `proof_for_contract` harness.
+    fn needs_harness(self) -> bool {
+        matches!(
+            self,
+            ContractKind::Requires | ContractKind::Ensures | ContractKind::Modifies
+        )
+    }
+}
+
+/// A function or type with contracts.
+#[derive(Debug, Serialize)]
+struct ContractItem {
+    /// Fully qualified path, e.g. `core::ptr::non_null::NonNull::add`.
+    /// Items defined by a `macro_rules!` macro are qualified by the macro, e.g.
+    /// `core::num::int_macros::int_impl!::unchecked_add`, since their self type is only known
+    /// once the macro is expanded.
+    path: String,
+    /// Path to the source file, relative to the library directory.
+    file: String,
+    contracts: BTreeSet<ContractKind>,
+    /// The `proof_for_contract` harnesses whose target resolves to this function.
+    harnesses: Vec<String>,
+    #[serde(skip)]
+    in_macro: bool,
+}
+
+impl ContractItem {
+    fn needs_harness(&self) -> bool {
+        self.contracts.iter().any(|kind| kind.needs_harness())
+    }
+}
+
+/// A `proof_for_contract` harness.
+#[derive(Debug, Serialize)]
+struct Harness {
+    path: String,
+    file: String,
+    /// The target of the harness, as written in the attribute.
+    target: String,
+    /// Whether the target resolves to a function with a `requires`, `ensures` or `modifies`
+    /// contract.
+    /// Absent if the name of the target is a macro metavariable, which is only known once the
+    /// macro is expanded.
+    has_contract: Option<bool>,
+    #[serde(skip)]
+    module: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Inventory {
+    items: Vec<ContractItem>,
+    harnesses: Vec<Harness>,
+    /// The contracts of the methods of the traits with a `trait_contracts` attribute, by trait
+    /// and method name.
+    #[serde(skip)]
+    trait_contracts: HashMap<(String, String), BTreeSet<ContractKind>>,
+    /// The impl methods that take the contracts of a trait method.
+    #[serde(skip)]
+    inherited_contracts: Vec<InheritedContracts>,
+}
+
+/// An impl method that takes the contracts of the method of the same name of a trait with a
+/// `trait_contracts` attribute, which are only known once all files are visited.
+struct InheritedContracts {
+    path: String,
+    file: String,
+    trait_name: String,
+    method: String,
+    in_macro: bool,
+}
+
+/// What the attributes of an item declare.
+#[derive(Default)]
+struct Annotations {
+    contracts: BTreeSet<ContractKind>,
+    /// The target of a `kani::proof_for_contract` attribute.
+    harness_target: Option<String>,
+    /// Whether the item has a `check_invariant` attribute.
+    check_invariant: bool,
+    /// The name of the trait of a `trait_contracts` attribute, which is empty if the attribute
+    /// does not name one.
+    trait_contracts: Option<String>,
+}
+
+/// The tokens of an attribute argument, without the whitespace that printing a token stream adds,
+/// except before a word that follows a word or a closing bracket, e.g. in `<Range<usize> as Step>`.
+fn compact(tokens: &TokenStream) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let printed = tokens.to_string();
+    let mut compacted = String::new();
+    for word in printed.split_whitespace() {
+        if compacted.ends_with(|c: char| is_word_char(c) || matches!(c, '>' | ')' | ']'))
+            && word.starts_with(is_word_char)
+        {
+            compacted.push(' ');
+        }
+        compacted.push_str(word);
+    }
+    compacted
+}
+
+/// Record what `meta` declares in `annotations`, including the attributes of a `cfg_attr`.
+fn add_annotation(meta: &Meta, annotations: &mut Annotations) {
+    let path = meta
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+    let kind = match path.as_slice() {
+        ["requires"] | ["safety", "requires"] => Some(ContractKind::Requires),
+        ["ensures"] | ["safety", "ensures"] => Some(ContractKind::Ensures),
+        ["modifies"] | ["safety", "modifies"] => Some(ContractKind::Modifies),
+        ["invariant"] | ["safety", "invariant"] => Some(ContractKind::Invariant),
+        ["loop_invariant"] | ["safety", "loop_invariant"] => Some(ContractKind::LoopInvariant),
+        ["derive"] => match meta {
+            Meta::List(list)
+                if list.tokens.clone().into_iter().any(
+                    |token| matches!(&token, TokenTree::Ident(ident) if ident == "Invariant"),
+                ) =>
+            {
+                Some(ContractKind::DeriveInvariant)
+            }
+            _ => None,
+        },
+        ["check_invariant"] | ["safety", "check_invariant"] => {
+            annotations.check_invariant = true;
+            None
+        }
+        ["trait_contracts"] | ["safety", "trait_contracts"] => {
+            let trait_path = match meta {
+                Meta::List(list) => compact(&list.tokens),
+                _ => String::new(),
+            };
+            let trait_name = trait_path.rsplit("::").next().unwrap_or_default();
+            annotations.trait_contracts = Some(trait_name.to_string());
+            None
+        }
+        ["kani", "proof_for_contract"] => {
+            if let Meta::List(list) = meta {
+                annotations.harness_target = Some(compact(&list.tokens));
+            }
+            None
+        }
+        ["cfg_attr"] => {
+            // The attributes follow the configuration predicate
+            let attrs = match meta {
+                Meta::List(list) => list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok(),
+                _ => None,
+            };
+            for attr in attrs.iter().flatten().skip(1) {
+                add_annotation(attr, annotations);
+            }
+            None
+        }
+        _ => None,
+    };
+    annotations.contracts.extend(kind);
+}
+
+fn annotations(attrs: &[Attribute]) -> Annotations {
+    let mut annotations = Annotations::default();
+    for attr in attrs {
+        add_annotation(&attr.meta, &mut annotations);
+    }
+    annotations
+}
+
+/// Whether a function body contains a `loop_invariant`.
+#[derive(Default)]
+struct LoopInvariantFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for LoopInvariantFinder {
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        self.found |= annotations(std::slice::from_ref(attr))
+            .contracts
+            .contains(&ContractKind::LoopInvariant);
+    }
+}
+
+/// The contracts that a `check_invariant` attribute on `item_impl` adds to `impl_fn`: the safe
+/// methods of a trait impl and the public safe methods require the invariant of `self`, ensure it
+/// if they borrow `self`, and ensure the invariant of a returned `Self`.
+fn invariant_contracts(item_impl: &ItemImpl, impl_fn: &ImplItemFn) -> Vec<ContractKind> {
+    let sig = &impl_fn.sig;
+    let is_public = item_impl.trait_.is_some() || matches!(impl_fn.vis, Visibility::Public(_));
+    if !is_public || sig.unsafety.is_some() {
+        return Vec::new();
+    }
+    let mut contracts = Vec::new();
+    if let Some(receiver) = sig.receiver() {
+        contracts.push(ContractKind::Requires);
+        if receiver.reference.is_some() {
+            contracts.push(ContractKind::Ensures);
+        }
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        let ty = type_name(ty);
+        if ty == "Self" || ty == type_name(&item_impl.self_ty) {
+            contracts.push(ContractKind::Ensures);
+        }
+    }
+    contracts
+}
+
+/// A short name for a self type, in the style that rustc uses in def paths.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(TypePath { path, .. }) => path
+            .segments
+            .last()
+            .map_or_else(String::new, |segment| segment.ident.to_string()),
+        Type::Ptr(ptr) => {
+            let mutability = if ptr.mutability.is_some() {
+                "mut"
+            } else {
+                "const"
+            };
+            format!("*{mutability} {}", type_name(&ptr.elem))
+        }
+        Type::Reference(reference) => format!("&{}", type_name(&reference.elem)),
+        Type::Slice(slice) => format!("[{}]", type_name(&slice.elem)),
+        Type::Array(array) => format!("[{}; _]", type_name(&array.elem)),
+        Type::Tuple(tuple) => format!(
+            "({})",
+            tuple
+                .elems
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => "_".to_string(),
+    }
+}
+
+/// Collects the items with contracts and the harnesses of one source file.
+struct FileVisitor<'a> {
+    file: String,
+    module: Vec<String>,
+    inventory: &'a mut Inventory,
+}
+
+impl FileVisitor<'_> {
+    fn record(&mut self, path: String, mut annotations: Annotations, in_macro: bool) {
+        if let Some(trait_name) = annotations.trait_contracts.take() {
+            let method = path.rsplit("::").next().unwrap_or_default();
+            if !trait_name.is_empty() && !method.starts_with('$') {
+                self.inventory.inherited_contracts.push(InheritedContracts {
+                    path: path.clone(),
+                    file: self.file.clone(),
+                    trait_name,
+                    method: method.to_string(),
+                    in_macro,
+                });
+            }
+        }
+        if let Some(target) = annotations.harness_target.take() {
+            self.inventory.harnesses.push(Harness {
+                path: path.clone(),
+                file: self.file.clone(),
+                target,
+                has_contract: None,
+                module: self.module.clone(),
+            });
+        }
+        if !annotations.contracts.is_empty() {
+            self.inventory.items.push(ContractItem {
+                path,
+                file: self.file.clone(),
+                contracts: annotations.contracts,
+                harnesses: Vec::new(),
+                in_macro,
+            });
+        }
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        self.module
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(name))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    fn visit_items(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Fn(item_fn) => {
+                    let mut annotations = annotations(&item_fn.attrs);
+                    let mut finder = LoopInvariantFinder::default();
+                    finder.visit_block(&item_fn.block);
+                    if finder.found {
+                        annotations.contracts.insert(ContractKind::LoopInvariant);
+                    }
+                    let path = self.qualify(&item_fn.sig.ident.to_string());
+                    self.record(path, annotations, false);
+                }
+                Item::Impl(item_impl) => {
+                    let impl_annotations = annotations(&item_impl.attrs);
+                    let trait_name = item_impl.trait_.as_ref().and_then(|(_, trait_path, _)| {
+                        trait_path
+                            .segments
+                            .last()
+                            .map(|segment| segment.ident.to_string())
+                    });
+                    let self_ty = match &item_impl.trait_ {
+                        Some((_, trait_path, _)) => format!(
+                            "<{} as {}>",
+                            type_name(&item_impl.self_ty),
+                            trait_path
+                                .segments
+                                .last()
+                                .map_or_else(String::new, |segment| segment.ident.to_string())
+                        ),
+                        None => match &*item_impl.self_ty {
+                            Type::Path(_) => type_name(&item_impl.self_ty),
+                            self_ty => format!("<impl {}>", type_name(self_ty)),
+                        },
+                    };
+                    for impl_item in &item_impl.items {
+                        match impl_item {
+                            ImplItem::Fn(impl_fn) => {
+                                let mut annotations = annotations(&impl_fn.attrs);
+                                let mut finder = LoopInvariantFinder::default();
+                                finder.visit_block(&impl_fn.block);
+                                if finder.found {
+                                    annotations.contracts.insert(ContractKind::LoopInvariant);
+                                }
+                                if impl_annotations.check_invariant {
+                                    annotations
+                                        .contracts
+                                        .extend(invariant_contracts(item_impl, impl_fn));
+                                }
+                                if impl_annotations.trait_contracts.is_some() {
+                                    annotations.trait_contracts =
+                                        annotations.trait_contracts.or_else(|| trait_name.clone());
+                                }
+                                let path =
+                                    self.qualify(&format!("{self_ty}::{}", impl_fn.sig.ident));
+                                self.record(path, annotations, false);
+                            }
+                            ImplItem::Macro(impl_macro) => {
+                                self.visit_macro_tokens(
+                                    &self_ty,
+                                    impl_macro.mac.tokens.clone(),
+                                    None,
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Item::Trait(item_trait) => {
+                    let has_trait_contracts =
+                        annotations(&item_trait.attrs).trait_contracts.is_some();
+                    for trait_item in &item_trait.items {
+                        if let TraitItem::Fn(trait_fn) = trait_item {
+                            let annotations = annotations(&trait_fn.attrs);
+                            if has_trait_contracts {
+                                // The contracts are checked on the impls, which take them once all
+                                // files are visited
+                                self.inventory.trait_contracts.insert(
+                                    (item_trait.ident.to_string(), trait_fn.sig.ident.to_string()),
+                                    annotations.contracts,
+                                );
+                                continue;
+                            }
+                            let path = self
+                                .qualify(&format!("{}::{}", item_trait.ident, trait_fn.sig.ident));
+                            self.record(path, annotations, false);
+                        }
+                    }
+                }
+                Item::Struct(item_struct) => {
+                    let path = self.qualify(&item_struct.ident.to_string());
+                    self.record(path, annotations(&item_struct.attrs), false);
+                }
+                Item::Enum(item_enum) => {
+                    let path = self.qualify(&item_enum.ident.to_string());
+                    self.record(path, annotations(&item_enum.attrs), false);
+                }
+                Item::Mod(item_mod) => {
+                    if let Some((_, items)) = &item_mod.content {
+                        self.module.push(item_mod.ident.to_string());
+                        self.visit_items(items);
+                        self.module.pop();
+                    }
+                }
+                Item::Macro(item_macro) => {
+                    let scope = match &item_macro.ident {
+                        // The items of a `macro_rules!` are qualified by the macro
+                        Some(name) if item_macro.mac.path.is_ident("macro_rules") => {
+                            format!("{name}!")
+                        }
+                        // Items passed to a macro invocation are defined in the current module
+                        _ => String::new(),
+                    };
+                    self.visit_macro_tokens(&scope, item_macro.mac.tokens.clone(), None);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Find the annotated items in the tokens of a macro, which syn cannot parse as items.
+    ///
+    /// `scope` qualifies the names of the items found, and `enclosing_fn` is the index of the
+    /// item of the function whose body the tokens belong to, if any, to attribute loop invariants
+    /// to it.
+    fn visit_macro_tokens(
+        &mut self,
+        scope: &str,
+        tokens: TokenStream,
+        enclosing_fn: Option<usize>,
+    ) {
+        let in_macro = !scope.is_empty() && scope.ends_with('!');
+        let qualify = |visitor: &Self, name: &str| {
+            if scope.is_empty() {
+                visitor.qualify(name)
+            } else {
+                visitor.qualify(&format!("{scope}::{name}"))
+            }
+        };
+        let tokens = tokens.into_iter().collect::<Vec<_>>();
+        let mut pending = Annotations::default();
+        let mut index = 0;
+        while index < tokens.len() {
+            match &tokens[index] {
+                TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                    if let Some(TokenTree::Group(group)) = tokens.get(index + 1) {
+                        if group.delimiter() == Delimiter::Bracket {
+                            let attr_tokens: TokenStream =
+                                tokens[index..index + 2].iter().cloned().collect();
+                            if let Ok(attrs) = Attribute::parse_outer.parse2(attr_tokens) {
+                                let found = annotations(&attrs);
+                                if found.contracts.contains(&ContractKind::LoopInvariant) {
+                                    if let Some(fn_index) = enclosing_fn {
+                                        self.inventory.items[fn_index]
+                                            .contracts
+                                            .insert(ContractKind::LoopInvariant);
+                                    }
+                                }
+                                pending.contracts.extend(
+                                    found
+                                        .contracts
+                                        .into_iter()
+                                        .filter(|kind| *kind != ContractKind::LoopInvariant),
+                                );
+                                pending.harness_target =
+                                    found.harness_target.or(pending.harness_target);
+                                pending.trait_contracts =
+                                    found.trait_contracts.or(pending.trait_contracts);
+                            }
+                            index += 2;
+                            continue;
+                        }
+                    }
+                }
+                TokenTree::Ident(keyword)
+                    if keyword == "fn" || keyword == "struct" || keyword == "enum" =>
+                {
+                    // The name may be a metavariable, e.g. `fn $name()`
+                    let name = match (tokens.get(index + 1), tokens.get(index + 2)) {
+                        (Some(TokenTree::Ident(name)), _) => Some(name.to_string()),
+                        (Some(TokenTree::Punct(dollar)), Some(TokenTree::Ident(name)))
+                            if dollar.as_char() == '$' =>
+                        {
+                            Some(format!("${name}"))
+                        }
+                        _ => None,
+                    };
+                    if let Some(name) = name {
+                        let annotations = std::mem::take(&mut pending);
+                        let has_contracts = !annotations.contracts.is_empty();
+                        let path = qualify(self, &name);
+                        self.record(path, annotations, in_macro);
+                        if keyword == "fn" {
+                            // Attribute the loop invariants in the body to this function, creating
+                            // its entry if it has no other contract
+                            let body = tokens[index + 1..].iter().find_map(|token| match token {
+                                TokenTree::Group(group)
+                                    if group.delimiter() == Delimiter::Brace =>
+                                {
+                                    Some(group.stream())
+                                }
+                                _ => None,
+                            });
+                            if let Some(body) = body {
+                                if !has_contracts {
+                                    self.inventory.items.push(ContractItem {
+                                        path: qualify(self, &name),
+                                        file: self.file.clone(),
+                                        contracts: BTreeSet::new(),
+                                        harnesses: Vec::new(),
+                                        in_macro,
+                                    });
+                                }
+                                let fn_index = self.inventory.items.len() - 1;
+                                self.visit_macro_tokens(scope, body, Some(fn_index));
+                                if self.inventory.items[fn_index].contracts.is_empty() {
+                                    self.inventory.items.pop();
+                                }
+                                // Skip to the end of the body
+                                while !matches!(&tokens[index], TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+                                {
+                                    index += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                    pending = Annotations::default();
+                }
+                TokenTree::Group(group) => {
+                    self.visit_macro_tokens(scope, group.stream(), enclosing_fn);
+                    if group.delimiter() == Delimiter::Brace {
+                        pending = Annotations::default();
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+    }
+}
+
+/// The module path of a source file, e.g. `core::ptr::non_null` for
+/// `core/src/ptr/non_null.rs`, or `None` if the file is not in the `src` directory of a crate.
+fn module_path(relative_path: &Path) -> Option<Vec<String>> {
+    let mut components = relative_path
+        .iter()
+        .map(|component| component.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if components.get(1).map(String::as_str) != Some("src") {
+        return None;
+    }
+    components.remove(1);
+    let file_name = components.pop()?;
+    match file_name.strip_suffix(".rs")? {
+        "lib" | "mod" => {}
+        module => components.push(module.to_string()),
+    }
+    Some(components)
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The type segment of a function path, if the path has one, erasing the pointee of pointers
+/// so that e.g. `<*const ()>::add` matches `<impl *const T>::add`.
+fn self_type_key(segment: &str) -> Option<String> {
+    let is_type = segment.starts_with('*')
+        || segment.starts_with('[')
+        || segment.starts_with('&')
+        || segment.starts_with('(')
+        || segment.chars().next().is_some_and(char::is_uppercase)
+        || ARBITRARY_PRIMITIVES.contains(&segment);
+    if !is_type {
+        return None;
+    }
+    for pointer in ["*const", "*mut"] {
+        if segment.starts_with(pointer) {
+            return Some(pointer.to_string());
+        }
+    }
+    Some(segment.to_string())
+}
+
+/// Attach the contracts of the methods of `trait_contracts` traits to the impl methods that take
+/// them.
+fn resolve_trait_contracts(inventory: &mut Inventory) {
+    for inherited in std::mem::take(&mut inventory.inherited_contracts) {
+        let key = (inherited.trait_name, inherited.method);
+        let Some(contracts) = inventory.trait_contracts.get(&key) else {
+            continue;
+        };
+        if contracts.is_empty() {
+            continue;
+        }
+        match inventory
+            .items
+            .iter_mut()
+            .find(|item| item.path == inherited.path && item.file == inherited.file)
+        {
+            Some(item) => item.contracts.extend(contracts),
+            None => inventory.items.push(ContractItem {
+                path: inherited.path,
+                file: inherited.file,
+                contracts: contracts.clone(),
+                harnesses: Vec::new(),
+                in_macro: inherited.in_macro,
+            }),
+        }
+    }
+}
+
+/// Resolve the target of each harness to the functions with `requires`, `ensures` or `modifies`
+/// contracts.
+///
+/// A function matches if it has the name of the target, and the same self type if the target has
+/// one. Functions defined in `macro_rules!` macros and targets that use metavariables match any
+/// self type. If several functions match, the ones closest to the harness in the module tree win.
+fn resolve_harnesses(inventory: &mut Inventory) {
+    let item_segments = inventory
+        .items
+        .iter()
+        .map(|item| path_segments(&item.path))
+        .collect::<Vec<_>>();
+
+    for harness in &mut inventory.harnesses {
+        let target = path_segments(&harness.target);
+        let Some((name, qualifiers)) = target.split_last() else {
+            continue;
+        };
+        if name.starts_with('$') {
+            continue;
+        }
+        let target_type = qualifiers
+            .last()
+            .filter(|segment| !segment.starts_with('$'))
+            .and_then(|segment| self_type_key(segment));
+
+        let mut candidates = Vec::new();
+        for (index, (item, segments)) in inventory.items.iter().zip(&item_segments).enumerate() {
+            let Some((item_name, item_qualifiers)) = segments.split_last() else {
+                continue;
+            };
+            if !item.needs_harness() || item_name != name {
+                continue;
+            }
+            let item_type = item_qualifiers
+                .last()
+                .and_then(|segment| self_type_key(segment));
+            let types_match = item.in_macro
+                || match (&target_type, &item_type) {
+                    (Some(target_type), Some(item_type)) => target_type == item_type,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+            if types_match {
+                let common_prefix = segments
+                    .iter()
+                    .zip(&harness.module)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                candidates.push((common_prefix, index));
+            }
+        }
+
+        let closest = candidates
+            .iter()
+            .map(|(common_prefix, _)| *common_prefix)
+            .max();
+        harness.has_contract = Some(closest.is_some());
+        let Some(closest) = closest else {
+            continue;
+        };
+        for (_, index) in candidates
+            .into_iter()
+            .filter(|(common_prefix, _)| *common_prefix == closest)
+        {
+            inventory.items[index].harnesses.push(harness.path.clone());
+        }
+    }
+}
+
+/// List the functions and types with contracts in `library_path`, and the harnesses that verify
+/// them.
+fn inventory(library_path: &Path) -> Result<Inventory> {
+    let mut inventory = Inventory {
+        items: Vec::new(),
+        harnesses: Vec::new(),
+        trait_contracts: HashMap::new(),
+        inherited_contracts: Vec::new(),
+    };
+    for krate in CRATES {
+        let mut files = Vec::new();
+        rust_files(&library_path.join(krate).join("src"), &mut files)?;
+        files.sort();
+        for path in files {
+            let relative_path = path.strip_prefix(library_path)?;
+            let Some(module) = module_path(relative_path) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path)?;
+            let mut visitor = FileVisitor {
+                file: relative_path.to_string_lossy().into_owned(),
+                module,
+                inventory: &mut inventory,
+            };
+            match syn::parse_file(&source) {
+                Ok(file) => visitor.visit_items(&file.items),
+                // Nightly syntax that syn does not support yet, like `~const` bounds, makes the
+                // whole file fail to parse, so fall back to scanning its tokens like a macro body.
+                // The self types of the methods are then missing from their paths.
+                Err(err) => match source.parse::<TokenStream>() {
+                    Ok(tokens) => {
+                        println!(
+                            "[WARNING] Scanning the tokens of {}, which syn cannot parse: {err}",
+                            relative_path.display()
+                        );
+                        visitor.visit_macro_tokens("", tokens, None);
+                    }
+                    Err(err) => println!(
+                        "[WARNING] Unable to parse {}: {err}",
+                        relative_path.display()
+                    ),
+                },
+            }
+        }
+    }
+    resolve_trait_contracts(&mut inventory);
+    resolve_harnesses(&mut inventory);
+    Ok(inventory)
+}
+
+fn summary_table(inventory: &Inventory) -> Result<MarkdownTable> {
+    let kinds = [
+        ContractKind::Requires,
+        ContractKind::Ensures,
+        ContractKind::Modifies,
+        ContractKind::LoopInvariant,
+        ContractKind::Invariant,
+        ContractKind::DeriveInvariant,
+    ];
+    let rows = kinds
+        .iter()
+        .map(|kind| {
+            let count = inventory
+                .items
+                .iter()
+                .filter(|item| item.contracts.contains(kind))
+                .count();
+            vec![format!("Items with `{kind}`"), count.to_string()]
+        })
+        .chain([
+            vec![
+                "Contracts without harness".to_string(),
+                contracts_without_harness(inventory).count().to_string(),
+            ],
+            vec![
+                "Harnesses".to_string(),
+                inventory.harnesses.len().to_string(),
+            ],
+            vec![
+                "Harnesses whose target has no contract".to_string(),
+                harnesses_without_contract(inventory).count().to_string(),
+            ],
+        ])
+        .collect();
+    Ok(MarkdownTable::new(
+        Some(vec!["Contracts".to_string(), "#".to_string()]),
+        rows,
+    )?)
+}
+
+fn contracts_without_harness(inventory: &Inventory) -> impl Iterator<Item = &ContractItem> {
+    inventory
+        .items
+        .iter()
+        .filter(|item| item.needs_harness() && item.harnesses.is_empty())
+}
+
+fn harnesses_without_contract(inventory: &Inventory) -> impl Iterator<Item = &Harness> {
+    inventory
+        .harnesses
+        .iter()
+        .filter(|harness| harness.has_contract == Some(false))
+}
+
+fn items_table(inventory: &Inventory) -> Result<MarkdownTable> {
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Item with contracts".to_string(),
+            "Contracts".to_string(),
+            "File".to_string(),
+            "Harnesses".to_string(),
+        ]),
+        inventory
+            .items
+            .iter()
+            .map(|item| {
+                vec![
+                    item.path.clone(),
+                    item.contracts
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    item.file.clone(),
+                    if item.needs_harness() && item.harnesses.is_empty() {
+                        "None".to_string()
+                    } else {
+                        item.harnesses.join(", ")
+                    },
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+fn orphan_harnesses_table(inventory: &Inventory) -> Result<MarkdownTable> {
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Harness whose target has no contract".to_string(),
+            "Target".to_string(),
+            "File".to_string(),
+        ]),
+        harnesses_without_contract(inventory)
+            .map(|harness| {
+                vec![
+                    harness.path.clone(),
+                    harness.target.clone(),
+                    harness.file.clone(),
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+/// Report the contracts in the standard library and the harnesses that verify them.
+pub fn compute_contract_inventory(library_path: &Path, format: OutputFormat) -> Result<()> {
+    let inventory = inventory(library_path)?;
+
+    let out_path = Path::new("contract_inventory").with_extension(match format {
+        OutputFormat::Markdown => "md",
+        OutputFormat::Json => "json",
+    });
+    let mut out_file = File::create(&out_path)?;
+    match format {
+        OutputFormat::Markdown => {
+            write_table_to_file(&mut out_file, &summary_table(&inventory)?)?;
+            write_table_to_file(&mut out_file, &items_table(&inventory)?)?;
+            write_table_to_file(&mut out_file, &orphan_harnesses_table(&inventory)?)?;
+        }
+        OutputFormat::Json => serde_json::to_writer_pretty(out_file, &inventory)?,
+    }
+
+    let missing_count = contracts_without_harness(&inventory).count();
+    if missing_count > 0 {
+        println!("[WARNING] {missing_count} function(s) have a contract without harness");
+    }
+    let orphan_count = harnesses_without_contract(&inventory).count();
+    if orphan_count > 0 {
+        println!("[WARNING] {orphan_count} harness(es) target a function without contract");
+    }
+    println!("Wrote results to {}", out_path.to_string_lossy());
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use anyhow::Result;
+
+use std::{collections::BTreeMap, fs::File, path::Path};
+
+use serde::Serialize;
+use strum_macros::Display;
+use to_markdown_table::MarkdownTable;
+
+use crate::{
+    make_tables::write_table_to_file, AutoHarnessMetadata, AutoHarnessSkipReason, FunctionStatus,
+    OutputFormat,
+};
+
+/// How the automatic harness status of a function changed between two runs.
+/// The variants are ordered so that regressions come first in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+enum ChangeKind {
+    /// The function had an automatic harness, but no longer has one.
+    #[strum(serialize = "Lost automatic harness")]
+    LostHarness,
+    /// The function now has an automatic harness.
+    #[strum(serialize = "Gained automatic harness")]
+    GainedHarness,
+    /// The function is still skipped, but for a different reason.
+    #[strum(serialize = "Skip reason changed")]
+    SkipReasonChanged,
+    /// The function is new and skipped.
+    #[strum(serialize = "New skipped function")]
+    AddedSkipped,
+    /// The function was skipped and no longer exists.
+    #[strum(serialize = "Removed skipped function")]
+    RemovedSkipped,
+}
+
+#[derive(Serialize)]
+struct StatusChange {
+    name: String,
+    kind: ChangeKind,
+    /// Absent if the function does not exist in the old run.
+    old: Option<FunctionStatus>,
+    /// Absent if the function does not exist in the new run.
+    new: Option<FunctionStatus>,
+}
+
+fn classify(old: Option<&FunctionStatus>, new: Option<&FunctionStatus>) -> Option<ChangeKind> {
+    use FunctionStatus::{Chosen, Skipped};
+    match (old, new) {
+        (Some(Chosen), Some(Chosen)) => None,
+        (Some(Chosen), _) => Some(ChangeKind::LostHarness),
+        (_, Some(Chosen)) => Some(ChangeKind::GainedHarness),
+        (Some(Skipped(old_reason)), Some(Skipped(new_reason))) => {
+            (old_reason != new_reason).then_some(ChangeKind::SkipReasonChanged)
+        }
+        (None, Some(Skipped(_))) => Some(ChangeKind::AddedSkipped),
+        (Some(Skipped(_)), None) => Some(ChangeKind::RemovedSkipped),
+        (None, None) => None,
+    }
+}
+
+/// Human-readable description of a function status for the Markdown table.
+fn describe(status: Option<&FunctionStatus>) -> String {
+    match status {
+        None => "Absent".to_string(),
+        Some(FunctionStatus::Chosen) => "Automatic harness".to_string(),
+        Some(FunctionStatus::Skipped(
+            reason @ AutoHarnessSkipReason::MissingArbitraryImpl(args),
+        )) => {
+            format!(
+                "{reason} ({})",
+                args.iter()
+                    .map(|(name, ty)| format!("{name}: {ty}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        Some(FunctionStatus::Skipped(reason)) => reason.to_string(),
+    }
+}
+
+fn changes(old_md: &AutoHarnessMetadata, new_md: &AutoHarnessMetadata) -> Vec<StatusChange> {
+    let mut old_statuses = old_md.statuses();
+    let new_statuses = new_md.statuses();
+
+    let mut changes = Vec::new();
+    for (name, new) in new_statuses {
+        let old = old_statuses.remove(&name);
+        if let Some(kind) = classify(old.as_ref(), Some(&new)) {
+            changes.push(StatusChange {
+                name,
+                kind,
+                old,
+                new: Some(new),
+            });
+        }
+    }
+    // The remaining functions only exist in the old run
+    for (name, old) in old_statuses {
+        if let Some(kind) = classify(Some(&old), None) {
+            changes.push(StatusChange {
+                name,
+                kind,
+                old: Some(old),
+                new: None,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+    changes
+}
+
+fn summary_table(changes: &[StatusChange]) -> Result<MarkdownTable> {
+    let mut kind_count: BTreeMap<ChangeKind, u32> = BTreeMap::new();
+    for change in changes {
+        *kind_count.entry(change.kind).or_insert(0) += 1;
+    }
+
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Change in automatic harness status".to_string(),
+            "# of functions".to_string(),
+        ]),
+        kind_count
+            .iter()
+            .map(|(kind, count)| vec![kind.to_string(), count.to_string()])
+            .chain(std::iter::once(vec![
+                "Total".to_string(),
+                changes.len().to_string(),
+            ]))
+            .collect(),
+    )?)
+}
+
+fn changes_table(changes: &[StatusChange]) -> Result<MarkdownTable> {
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Function".to_string(),
+            "Change".to_string(),
+            "Before".to_string(),
+            "After".to_string(),
+        ]),
+        changes
+            .iter()
+            .map(|change| {
+                vec![
+                    change.name.clone(),
+                    change.kind.to_string(),
+                    describe(change.old.as_ref()),
+                    describe(change.new.as_ref()),
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+/// Report the functions whose automatic harness status changed from `old_md` to `new_md`.
+pub fn compute_diff(
+    old_md: &AutoHarnessMetadata,
+    new_md: &AutoHarnessMetadata,
+    format: OutputFormat,
+) -> Result<()> {
+    let changes = changes(old_md, new_md);
+
+    let out_path = Path::new("autoharness_diff").with_extension(match format {
+        OutputFormat::Markdown => "md",
+        OutputFormat::Json => "json",
+    });
+    let mut out_file = File::create(&out_path)?;
+    match format {
+        OutputFormat::Markdown => {
+            write_table_to_file(&mut out_file, &summary_table(&changes)?)?;
+            write_table_to_file(&mut out_file, &changes_table(&changes)?)?;
+        }
+        OutputFormat::Json => serde_json::to_writer_pretty(out_file, &changes)?,
+    }
+
+    let lost_count = changes
+        .iter()
+        .filter(|change| change.kind == ChangeKind::LostHarness)
+        .count();
+    if lost_count > 0 {
+        println!("[WARNING] {lost_count} function(s) lost their automatic harness");
+    }
+    println!("Wrote results to {}", out_path.to_string_lossy());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skipped(reason: AutoHarnessSkipReason) -> Option<FunctionStatus> {
+        Some(FunctionStatus::Skipped(reason))
+    }
+
+    #[test]
+    fn status_changes() {
+        use AutoHarnessSkipReason::{GenericFn, MissingArbitraryImpl, NoBody};
+        use ChangeKind::*;
+
+        let chosen = Some(FunctionStatus::Chosen);
+        let missing = |ty: &str| skipped(MissingArbitraryImpl(vec![("x".into(), ty.into())]));
+        let cases = [
+            (&chosen, &chosen, None),
+            (&chosen, &skipped(GenericFn), Some(LostHarness)),
+            (&chosen, &None, Some(LostHarness)),
+            (&skipped(NoBody), &chosen, Some(GainedHarness)),
+            (&None, &chosen, Some(GainedHarness)),
+            (&skipped(GenericFn), &skipped(GenericFn), None),
+            (
+                &skipped(GenericFn),
+                &skipped(NoBody),
+                Some(SkipReasonChanged),
+            ),
+            (&missing("u8"), &missing("u8"), None),
+            (
+                &missing("u8"),
+                &missing("Saturating<u8>"),
+                Some(SkipReasonChanged),
+            ),
+            (&None, &skipped(NoBody), Some(AddedSkipped)),
+            (&skipped(NoBody), &None, Some(RemovedSkipped)),
+            (&None, &None, None),
+        ];
+        for (old, new, expected) in cases {
+            assert_eq!(
+                classify(old.as_ref(), new.as_ref()),
+                expected,
+                "{old:?} -> {new:?}"
+            );
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use anyhow::Result;
-use clap::Parser;
+use challenges::{compute_challenge_coverage, read_challenge_coverage};
+use clap::{Parser, Subcommand, ValueEnum};
+use contract_inventory::compute_contract_inventory;
+use diff::compute_diff;
+use make_json::write_json;
 use make_tables::compute_metrics;
-use parse_scanner_output::process_scan_fns;
+use parse_scanner_output::{process_scan_fns, ScanFnsRow};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
@@ -10,11 +14,86 @@ use std::{
     path::Path,
 };
 use strum_macros::{Display, EnumString};
+use verification_results::{read_verification_results, HarnessResult};
 
+mod challenges;
+mod contract_inventory;
+mod diff;
+mod make_json;
 mod make_tables;
+mod missing_arbitrary;
 mod parse_scanner_output;
+mod verification_results;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    args: Option<Args>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Report the functions whose automatic harness status changed between two runs
+    Diff(DiffArgs),
+    /// Report which functions listed by the verification challenges have contracts and harnesses
+    Challenges(ChallengesArgs),
+    /// List the functions and types with contracts in the standard library, and their harnesses
+    Contracts(ContractsArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct DiffArgs {
+    /// Path to directory with the kani_metadata.json files of the old run
+    old_metadata_dir_path: String,
+
+    /// Path to directory with the kani_metadata.json files of the new run
+    new_metadata_dir_path: String,
+
+    /// Process only the specified crate
+    #[arg(long, value_name = "CRATE")]
+    for_crate: Option<String>,
+
+    /// Format of the output file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+}
+
+#[derive(clap::Args, Debug)]
+struct ChallengesArgs {
+    /// Path to the directory with the challenge descriptions, i.e., doc/src/challenges
+    challenges_dir_path: String,
+
+    /// Path to the JSON file produced by `kani list --format json`
+    kani_list_path: String,
+
+    /// Format of the output file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+}
+
+#[derive(clap::Args, Debug)]
+struct ContractsArgs {
+    /// Path to the library directory of this repository
+    library_path: String,
+
+    /// Format of the output file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputFormat {
+    /// Markdown tables
+    Markdown,
+    /// JSON records, one per function
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
 struct Args {
     /// Path to directory with kani_metadata.json files
     #[arg(required = true)]
@@ -39,6 +118,14 @@ struct Args {
     /// Only output data for unsafe functions
     #[arg(long)]
     unsafe_fns_only: bool,
+
+    /// Format of the output file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+
+    /// Path to the verification results JSON file produced by kani-std-analysis/log_parser.py
+    #[arg(long, value_name = "FILE")]
+    verification_results: Option<String>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutoHarnessMetadata {
@@ -50,7 +137,7 @@ pub struct AutoHarnessMetadata {
 }
 
 /// Reasons that Kani does not generate an automatic harness for a function.
-#[derive(Debug, Clone, Serialize, Deserialize, Display, EnumString)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
 pub enum AutoHarnessSkipReason {
     /// The function is generic.
     #[strum(serialize = "Generic Function")]
@@ -88,17 +175,62 @@ impl AutoHarnessMetadata {
         self.chosen.extend(other.chosen);
         self.skipped.extend(other.skipped);
     }
+
+    /// Restrict the metadata to the functions that the scanner reports as unsafe.
+    pub fn unsafe_only(&self, fn_to_row_data: &HashMap<String, ScanFnsRow>) -> Self {
+        let is_unsafe = |fn_name: &String| {
+            fn_to_row_data
+                .get(fn_name)
+                .map(|row| row.is_unsafe)
+                .unwrap_or(false)
+        };
+        Self {
+            chosen: self
+                .chosen
+                .iter()
+                .filter(|fn_name| is_unsafe(fn_name))
+                .cloned()
+                .collect(),
+            skipped: self
+                .skipped
+                .iter()
+                .filter(|(fn_name, _)| is_unsafe(fn_name))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Map every function to whether it got an automatic harness, ordered by function name.
+    pub fn statuses(&self) -> BTreeMap<String, FunctionStatus> {
+        let chosen = self
+            .chosen
+            .iter()
+            .map(|fn_name| (fn_name.clone(), FunctionStatus::Chosen));
+        let skipped = self
+            .skipped
+            .iter()
+            .map(|(fn_name, reason)| (fn_name.clone(), FunctionStatus::Skipped(reason.clone())));
+        chosen.chain(skipped).collect()
+    }
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+/// Whether Kani generated an automatic harness for a function.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "skip_reason", rename_all = "snake_case")]
+pub enum FunctionStatus {
+    Chosen,
+    Skipped(AutoHarnessSkipReason),
+}
 
-    // Collection of data from all crates
-    let mut cross_crate_fn_to_row_data = HashMap::new();
-    let mut cross_crate_autoharness_md = AutoHarnessMetadata::new();
+/// Read the autoharness metadata of every crate in `metadata_dir_path`, or only of `for_crate`.
+fn read_metadata(
+    metadata_dir_path: &str,
+    for_crate: Option<&str>,
+) -> Result<Vec<(String, AutoHarnessMetadata)>> {
+    let mut crates = Vec::new();
 
     // Iterate over all kani-metadata.json files; one per crate
-    for entry in fs::read_dir(&args.metadata_dir_path)? {
+    for entry in fs::read_dir(metadata_dir_path)? {
         let entry = entry?;
         let path = entry.path();
         if !path.to_string_lossy().contains("kani-metadata.json") {
@@ -111,12 +243,89 @@ fn main() -> Result<()> {
         let crate_name = v["crate_name"].as_str().unwrap();
 
         // Skip if a specific crate was requested and this isn't it
-        if let Some(ref target_crate) = args.for_crate {
-            if target_crate != crate_name {
-                continue;
-            }
+        if for_crate.is_some_and(|target_crate| target_crate != crate_name) {
+            continue;
+        }
+
+        let autoharness_md: AutoHarnessMetadata =
+            serde_json::from_value(v["autoharness_md"].clone())?;
+        crates.push((crate_name.to_string(), autoharness_md));
+    }
+
+    Ok(crates)
+}
+
+fn write_results(
+    crate_name: &str,
+    autoharness_md: &AutoHarnessMetadata,
+    fn_to_row_data: &HashMap<String, ScanFnsRow>,
+    verification_results: Option<&HashMap<String, HarnessResult>>,
+    args: &Args,
+) -> Result<()> {
+    match args.format {
+        OutputFormat::Markdown => compute_metrics(
+            crate_name,
+            autoharness_md,
+            fn_to_row_data,
+            args.show_precise_types,
+            args.unsafe_fns_only,
+            verification_results,
+        ),
+        OutputFormat::Json => write_json(
+            crate_name,
+            autoharness_md,
+            fn_to_row_data,
+            args.unsafe_fns_only,
+        ),
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    if let Some(Command::Challenges(challenges_args)) = cli.command {
+        let challenges = read_challenge_coverage(
+            Path::new(&challenges_args.challenges_dir_path),
+            Path::new(&challenges_args.kani_list_path),
+        )?;
+        return compute_challenge_coverage(&challenges, challenges_args.format);
+    }
+    if let Some(Command::Contracts(contracts_args)) = cli.command {
+        return compute_contract_inventory(
+            Path::new(&contracts_args.library_path),
+            contracts_args.format,
+        );
+    }
+    if let Some(Command::Diff(diff_args)) = cli.command {
+        let for_crate = diff_args.for_crate.as_deref();
+        let mut old_md = AutoHarnessMetadata::new();
+        for (_, autoharness_md) in read_metadata(&diff_args.old_metadata_dir_path, for_crate)? {
+            old_md.extend(autoharness_md);
+        }
+        let mut new_md = AutoHarnessMetadata::new();
+        for (_, autoharness_md) in read_metadata(&diff_args.new_metadata_dir_path, for_crate)? {
+            new_md.extend(autoharness_md);
         }
+        return compute_diff(&old_md, &new_md, diff_args.format);
+    }
+
+    // Without a subcommand, clap requires the arguments of the analysis
+    let args = cli.args.expect("missing analysis arguments");
 
+    let verification_results = args
+        .verification_results
+        .as_ref()
+        .map(|path| read_verification_results(Path::new(path)))
+        .transpose()?;
+    let verification_results = verification_results.as_ref();
+
+    // Collection of data from all crates
+    let mut cross_crate_fn_to_row_data = HashMap::new();
+    let mut cross_crate_autoharness_md = AutoHarnessMetadata::new();
+
+    for (crate_name, autoharness_md) in
+        read_metadata(&args.metadata_dir_path, args.for_crate.as_deref())?
+    {
         println!("Processing crate {crate_name}");
 
         let scanner_fn_csv = format!(
@@ -126,25 +335,22 @@ fn main() -> Result<()> {
         let scanner_fn_csv_path = Path::new(&scanner_fn_csv);
         let fn_to_row_data = process_scan_fns(scanner_fn_csv_path)?;
 
-        let autoharness_md: AutoHarnessMetadata =
-            serde_json::from_value(v["autoharness_md"].clone())?;
-
         if args.per_crate {
             // Process each crate separately
-            compute_metrics(
-                crate_name,
+            write_results(
+                &crate_name,
                 &autoharness_md,
                 &fn_to_row_data,
-                args.show_precise_types,
-                args.unsafe_fns_only,
+                verification_results,
+                &args,
             )?;
         } else if args.for_crate.is_some() {
-            return compute_metrics(
-                crate_name,
+            return write_results(
+                &crate_name,
                 &autoharness_md,
                 &fn_to_row_data,
-                args.show_precise_types,
-                args.unsafe_fns_only,
+                verification_results,
+                &args,
             );
         } else {
             cross_crate_fn_to_row_data.extend(fn_to_row_data);
@@ -154,12 +360,12 @@ fn main() -> Result<()> {
 
     // Process combined data if not doing per-crate or single-crate analysis
     if !args.per_crate {
-        compute_metrics(
+        write_results(
             "all_crates",
             &cross_crate_autoharness_md,
             &cross_crate_fn_to_row_data,
-            args.show_precise_types,
-            args.unsafe_fns_only,
+            verification_results,
+            &args,
         )?;
     }
 
@@ -0,0 +1,71 @@
+use anyhow::Result;
+
+use std::{collections::HashMap, fs::File, path::Path};
+
+use serde::Serialize;
+
+use crate::{parse_scanner_output::ScanFnsRow, AutoHarnessMetadata, FunctionStatus};
+
+/// Machine-readable analysis results for one crate, or for all crates combined.
+#[derive(Serialize)]
+struct CrateRecords<'a> {
+    crate_name: &'a str,
+    functions: Vec<FunctionRecord<'a>>,
+}
+
+/// Whether a function got an automatic harness, along with its safety flags from the scanner.
+#[derive(Serialize)]
+struct FunctionRecord<'a> {
+    name: String,
+    #[serde(flatten)]
+    status: FunctionStatus,
+    /// Absent if the function is missing from the scanner tool output.
+    #[serde(flatten)]
+    scanner: Option<&'a ScanFnsRow>,
+}
+
+/// Write the analysis results as JSON records, one per function, ordered by function name.
+pub fn write_json(
+    crate_name: &str,
+    autoharness_md: &AutoHarnessMetadata,
+    fn_to_row_data: &HashMap<String, ScanFnsRow>,
+    unsafe_fns_only: bool,
+) -> Result<()> {
+    let autoharness_md = if unsafe_fns_only {
+        autoharness_md.unsafe_only(fn_to_row_data)
+    } else {
+        autoharness_md.clone()
+    };
+
+    let functions = autoharness_md
+        .statuses()
+        .into_iter()
+        .map(|(name, status)| {
+            let scanner = fn_to_row_data.get(&name);
+            FunctionRecord {
+                name,
+                status,
+                scanner,
+            }
+        })
+        .collect();
+
+    let out_path = Path::new(&format!(
+        "{}{}_autoharness_data",
+        crate_name,
+        if unsafe_fns_only { "_unsafe" } else { "" }
+    ))
+    .with_extension("json");
+    let out_file = File::create(&out_path)?;
+    serde_json::to_writer_pretty(
+        out_file,
+        &CrateRecords {
+            crate_name,
+            functions,
+        },
+    )?;
+
+    println!("Wrote results to {}", out_path.to_string_lossy());
+
+    Ok(())
+}
@@ -9,7 +9,12 @@ use std::{
 
 use to_markdown_table::MarkdownTable;
 
-use crate::{parse_scanner_output::ScanFnsRow, AutoHarnessMetadata, AutoHarnessSkipReason};
+use crate::{
+    missing_arbitrary::missing_arbitrary_ranking_table,
+    parse_scanner_output::ScanFnsRow,
+    verification_results::{slowest_harnesses_table, verification_results_table, HarnessResult},
+    AutoHarnessMetadata, AutoHarnessSkipReason,
+};
 
 /// Create Markdown tables with the analysis results
 fn chosen_overview_table(
@@ -191,7 +196,7 @@ fn skipped_breakdown_table(
     )?)
 }
 
-fn write_table_to_file(out_file: &mut File, table: &MarkdownTable) -> Result<()> {
+pub fn write_table_to_file(out_file: &mut File, table: &MarkdownTable) -> Result<()> {
     let mut table_as_string = table.to_string();
     table_as_string.push('\n');
     Ok(out_file.write_all(table_as_string.as_bytes())?)
@@ -203,32 +208,10 @@ pub fn compute_metrics(
     fn_to_row_data: &HashMap<String, ScanFnsRow>,
     show_precise_types: bool,
     unsafe_fns_only: bool,
+    verification_results: Option<&HashMap<String, HarnessResult>>,
 ) -> Result<()> {
     let unsafe_metadata = if unsafe_fns_only {
-        AutoHarnessMetadata {
-            chosen: autoharness_md
-                .chosen
-                .iter()
-                .filter(|fn_name| {
-                    fn_to_row_data
-                        .get(*fn_name)
-                        .map(|row| row.is_unsafe)
-                        .unwrap_or(false)
-                })
-                .cloned()
-                .collect(),
-            skipped: autoharness_md
-                .skipped
-                .iter()
-                .filter(|(fn_name, _)| {
-                    fn_to_row_data
-                        .get(*fn_name)
-                        .map(|row| row.is_unsafe)
-                        .unwrap_or(false)
-                })
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-        }
+        autoharness_md.unsafe_only(fn_to_row_data)
     } else {
         autoharness_md.clone()
     };
@@ -236,6 +219,8 @@ pub fn compute_metrics(
     let chosen_overview_table = chosen_overview_table(&unsafe_metadata, fn_to_row_data)?;
     let skipped_overview_table = skipped_overview_table(&unsafe_metadata)?;
     let skipped_breakdown_table = skipped_breakdown_table(&unsafe_metadata, show_precise_types)?;
+    let missing_arbitrary_ranking_table =
+        missing_arbitrary_ranking_table(&unsafe_metadata, fn_to_row_data)?;
 
     let out_path = Path::new(&format!(
         "{}{}_autoharness_data",
@@ -246,8 +231,24 @@ pub fn compute_metrics(
     let mut out_file = File::create(&out_path)?;
 
     write_table_to_file(&mut out_file, &chosen_overview_table)?;
+    if let Some(verification_results) = verification_results {
+        write_table_to_file(
+            &mut out_file,
+            &verification_results_table(
+                crate_name,
+                &unsafe_metadata,
+                fn_to_row_data,
+                verification_results,
+            )?,
+        )?;
+        write_table_to_file(
+            &mut out_file,
+            &slowest_harnesses_table(&unsafe_metadata, verification_results)?,
+        )?;
+    }
     write_table_to_file(&mut out_file, &skipped_overview_table)?;
     write_table_to_file(&mut out_file, &skipped_breakdown_table)?;
+    write_table_to_file(&mut out_file, &missing_arbitrary_ranking_table)?;
 
     println!("Wrote results to {}", out_path.to_string_lossy());
 
@@ -0,0 +1,223 @@
+use anyhow::Result;
+
+use std::collections::{BTreeSet, HashMap};
+
+use to_markdown_table::MarkdownTable;
+
+use crate::{parse_scanner_output::ScanFnsRow, AutoHarnessMetadata, AutoHarnessSkipReason};
+
+// Generic types that implement kani::Arbitrary whenever their type arguments do,
+// so the types that block a harness are their arguments.
+const TRANSPARENT_WRAPPERS: [&str; 2] = ["Option", "Result"];
+
+// Types that implement kani::Arbitrary, which may appear inside a composite argument type.
+pub const ARBITRARY_PRIMITIVES: [&str; 18] = [
+    "bool", "char", "f16", "f32", "f64", "f128", "i8", "i16", "i32", "i64", "i128", "isize", "u8",
+    "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Split `s` on the occurrences of `separator` that are not nested in brackets, e.g. the `,` in a
+/// tuple type or the `::` in a function path.
+pub fn split_top_level<'a>(s: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (index, c) in s.char_indices() {
+        // Skip the rest of a separator that has just been split on
+        if index < start {
+            continue;
+        }
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            // Don't mistake the arrow of a function pointer type for a closing bracket
+            '>' if prev == '-' => {}
+            '>' | ')' | ']' => depth -= 1,
+            _ if depth == 0 && s[index..].starts_with(separator) => {
+                parts.push(&s[start..index]);
+                start = index + separator.len();
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(&s[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Normalize an argument type into the types that need a kani::Arbitrary implementation.
+///
+/// References are removed, since the automatic harness creates the referenced value,
+/// and arrays, tuples and transparent wrappers are replaced by their element types.
+/// The type arguments of other generic types are erased, so that e.g. `Saturating<i16>`
+/// and `Saturating<u8>` are counted as `Saturating<_>`, which a single generic impl unblocks.
+fn blocking_types(arg_type: &str) -> Vec<String> {
+    let arg_type = arg_type.trim();
+
+    if let Some(referent) = arg_type.strip_prefix('&') {
+        let referent = referent.trim_start();
+        // Remove the lifetime, if any
+        let referent = match referent.strip_prefix('\'') {
+            Some(lifetime_and_type) => lifetime_and_type
+                .split_once(' ')
+                .map_or(lifetime_and_type, |(_, ty)| ty),
+            None => referent,
+        };
+        let referent = referent.strip_prefix("mut ").unwrap_or(referent);
+        return blocking_types(referent);
+    }
+    for pointer in ["*const ", "*mut "] {
+        if arg_type.starts_with(pointer) {
+            return vec![format!("{pointer}_")];
+        }
+    }
+    if let Some(elements) = arg_type
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return match split_top_level(elements, ";").as_slice() {
+            [element, _length] => blocking_types(element),
+            _ => vec!["[_]".to_string()],
+        };
+    }
+    if let Some(elements) = arg_type
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return split_top_level(elements, ",")
+            .into_iter()
+            .flat_map(blocking_types)
+            .collect();
+    }
+    if let Some((path, type_args)) = arg_type
+        .strip_suffix('>')
+        .and_then(|rest| rest.split_once('<'))
+    {
+        let name = path.rsplit("::").next().unwrap_or(path);
+        let type_args = split_top_level(type_args, ",");
+        if TRANSPARENT_WRAPPERS.contains(&name) {
+            return type_args.into_iter().flat_map(blocking_types).collect();
+        }
+        let erased_args = vec!["_"; type_args.len()].join(", ");
+        return vec![format!("{path}<{erased_args}>")];
+    }
+
+    if ARBITRARY_PRIMITIVES.contains(&arg_type) {
+        return Vec::new();
+    }
+    vec![arg_type.to_string()]
+}
+
+/// Number of functions that a missing kani::Arbitrary implementation is involved in.
+#[derive(Default)]
+struct BlockedCount {
+    /// Functions with an argument that needs the implementation.
+    blocked: u32,
+    /// Unsafe functions with an argument that needs the implementation.
+    unsafe_blocked: u32,
+    /// Functions for which this is the only missing implementation.
+    unblocked: u32,
+    /// Unsafe functions for which this is the only missing implementation.
+    unsafe_unblocked: u32,
+}
+
+/// Rank the types that lack a kani::Arbitrary implementation by how many automatic harnesses
+/// implementing it would unblock, then by how many functions they are involved in.
+pub fn missing_arbitrary_ranking_table(
+    autoharness_md: &AutoHarnessMetadata,
+    fn_to_row_data: &HashMap<String, ScanFnsRow>,
+) -> Result<MarkdownTable> {
+    let mut type_to_count: HashMap<String, BlockedCount> = HashMap::new();
+
+    for (fn_name, reason) in &autoharness_md.skipped {
+        let AutoHarnessSkipReason::MissingArbitraryImpl(args) = reason else {
+            continue;
+        };
+        let is_unsafe = fn_to_row_data
+            .get(fn_name)
+            .map(|row| row.is_unsafe)
+            .unwrap_or(false);
+        // Count each type once per function, even if several arguments need it
+        let missing_types: BTreeSet<String> = args
+            .iter()
+            .flat_map(|(_, arg_type)| blocking_types(arg_type))
+            .collect();
+        let is_sole_blocker = missing_types.len() == 1;
+        for missing_type in missing_types {
+            let count = type_to_count.entry(missing_type).or_default();
+            count.blocked += 1;
+            count.unsafe_blocked += u32::from(is_unsafe);
+            count.unblocked += u32::from(is_sole_blocker);
+            count.unsafe_unblocked += u32::from(is_unsafe && is_sole_blocker);
+        }
+    }
+
+    let mut ranking = type_to_count.into_iter().collect::<Vec<_>>();
+    ranking.sort_by(|(type_a, count_a), (type_b, count_b)| {
+        (count_b.unblocked, count_b.blocked, type_a).cmp(&(
+            count_a.unblocked,
+            count_a.blocked,
+            type_b,
+        ))
+    });
+
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Type missing an Arbitrary implementation".to_string(),
+            "# of functions unblocked by implementing it".to_string(),
+            "# of unsafe functions unblocked".to_string(),
+            "# of functions blocked".to_string(),
+            "# of unsafe functions blocked".to_string(),
+        ]),
+        ranking
+            .into_iter()
+            .map(|(missing_type, count)| {
+                vec![
+                    missing_type,
+                    count.unblocked.to_string(),
+                    count.unsafe_unblocked.to_string(),
+                    count.blocked.to_string(),
+                    count.unsafe_blocked.to_string(),
+                ]
+            })
+            .collect(),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_outside_brackets() {
+        assert_eq!(split_top_level("a, b ,c", ","), ["a", "b", "c"]);
+        assert_eq!(
+            split_top_level("HashMap<K, V>, (u8, u16), [T; 2]", ","),
+            ["HashMap<K, V>", "(u8, u16)", "[T; 2]"]
+        );
+        assert_eq!(
+            split_top_level("fn(u8) -> u16, bool", ","),
+            ["fn(u8) -> u16", "bool"]
+        );
+        assert_eq!(
+            split_top_level("core::str::<impl Iterator for Chars<'a>>::next", "::"),
+            ["core", "str", "<impl Iterator for Chars<'a>>", "next"]
+        );
+        assert_eq!(
+            split_top_level("<Chars<'a> as core::iter::Iterator>::next", "::"),
+            ["<Chars<'a> as core::iter::Iterator>", "next"]
+        );
+    }
+
+    #[test]
+    fn empty_parts_are_dropped() {
+        assert_eq!(split_top_level("", ","), Vec::<&str>::new());
+        assert_eq!(split_top_level("(u8,)", ","), ["(u8,)"]);
+        assert_eq!(split_top_level("u8,", ","), ["u8"]);
+        assert_eq!(split_top_level("::core::ptr", "::"), ["core", "ptr"]);
+    }
+}
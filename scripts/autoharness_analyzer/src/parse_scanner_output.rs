@@ -1,6 +1,6 @@
 use anyhow::bail;
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufRead;
@@ -14,9 +14,10 @@ use std::path::Path;
 const SCANNER_COLS: usize = 6;
 
 /// Single row of data in {crate_name}_scan_functions.csv
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct ScanFnsRow {
+    #[serde(skip_serializing)]
     pub name: String,
     pub is_unsafe: bool,
     pub has_unsafe_ops: bool,
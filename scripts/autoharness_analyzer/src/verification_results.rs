@@ -0,0 +1,208 @@
+use anyhow::Result;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+use strum_macros::Display;
+use to_markdown_table::MarkdownTable;
+
+use crate::{parse_scanner_output::ScanFnsRow, AutoHarnessMetadata};
+
+// Number of harnesses listed in the table of slowest harnesses.
+const SLOWEST_HARNESSES_COUNT: usize = 10;
+
+/// Single entry of the JSON array produced by scripts/kani-std-analysis/log_parser.py
+#[derive(Debug, Deserialize)]
+struct LogParserEntry {
+    result: HarnessResult,
+}
+
+/// Outcome of running a single harness, as extracted from the Kani log by log_parser.py
+#[derive(Debug, Deserialize)]
+pub struct HarnessResult {
+    harness: String,
+    is_autoharness: bool,
+    /// Either a crate name, or a list of them if the scanner found the function in several crates
+    #[serde(rename = "crate")]
+    crate_name: Value,
+    /// "SUCCESSFUL" or "FAILED", absent if the harness did not run
+    result: Option<String>,
+    /// Verification time, e.g. "1.234s", or "TO" if CBMC timed out
+    time: Option<String>,
+    output: Vec<String>,
+}
+
+impl HarnessResult {
+    fn status(&self) -> VerificationStatus {
+        if self.time.as_deref() == Some("TO") {
+            return VerificationStatus::Timeout;
+        }
+        match self.result.as_deref() {
+            Some("SUCCESSFUL") => VerificationStatus::Verified,
+            // Kani reports unsupported constructs as failed properties,
+            // so we can only tell them apart from actual failures by their output
+            Some(_) if self.output.iter().any(|line| line.contains("unsupported")) => {
+                VerificationStatus::Unsupported
+            }
+            Some(_) => VerificationStatus::Failed,
+            None => VerificationStatus::NoResult,
+        }
+    }
+
+    /// Verification time in seconds, if the harness completed.
+    fn seconds(&self) -> Option<f64> {
+        self.time.as_deref()?.strip_suffix('s')?.parse().ok()
+    }
+
+    fn crate_name(&self) -> Option<&str> {
+        self.crate_name.as_str()
+    }
+}
+
+/// Outcome of verifying a function with its automatic harness.
+/// The variants are ordered like the columns of the results table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+enum VerificationStatus {
+    Verified,
+    Failed,
+    Timeout,
+    Unsupported,
+    #[strum(serialize = "No result")]
+    NoResult,
+}
+
+const STATUSES: [VerificationStatus; 5] = [
+    VerificationStatus::Verified,
+    VerificationStatus::Failed,
+    VerificationStatus::Timeout,
+    VerificationStatus::Unsupported,
+    VerificationStatus::NoResult,
+];
+
+/// Map the name of each function verified with an automatic harness to its verification result.
+pub fn read_verification_results(file_path: &Path) -> Result<HashMap<String, HarnessResult>> {
+    let results_file_data =
+        fs::read_to_string(file_path).unwrap_or_else(|_| panic!("Unable to read {file_path:?}"));
+    let entries: Vec<LogParserEntry> = serde_json::from_str(&results_file_data)?;
+
+    Ok(entries
+        .into_iter()
+        .filter(|entry| entry.result.is_autoharness)
+        .map(|entry| (entry.result.harness.clone(), entry.result))
+        .collect())
+}
+
+fn safety_class(row: &ScanFnsRow) -> &'static str {
+    if row.is_unsafe {
+        "Unsafe Functions"
+    } else if row.has_unsafe_ops {
+        "Safe Abstractions"
+    } else {
+        "Safe Functions"
+    }
+}
+
+/// Split the functions with automatic harnesses by verification result, per crate and per
+/// safe/unsafe class.
+pub fn verification_results_table(
+    crate_name: &str,
+    autoharness_md: &AutoHarnessMetadata,
+    fn_to_row_data: &HashMap<String, ScanFnsRow>,
+    verification_results: &HashMap<String, HarnessResult>,
+) -> Result<MarkdownTable> {
+    // Map (crate, class) to the number of functions with each verification status
+    let mut counts: BTreeMap<(String, &str), BTreeMap<VerificationStatus, u32>> = BTreeMap::new();
+
+    for fn_name in &autoharness_md.chosen {
+        // Functions missing from the scanner output are reported by the chosen overview table
+        let Some(row) = fn_to_row_data.get(fn_name) else {
+            continue;
+        };
+        let result = verification_results.get(fn_name);
+        let status = result.map_or(VerificationStatus::NoResult, HarnessResult::status);
+        let fn_crate = result
+            .and_then(HarnessResult::crate_name)
+            .unwrap_or(crate_name);
+        *counts
+            .entry((fn_crate.to_string(), safety_class(row)))
+            .or_default()
+            .entry(status)
+            .or_insert(0) += 1;
+    }
+
+    let mut totals: BTreeMap<VerificationStatus, u32> = BTreeMap::new();
+    for status_counts in counts.values() {
+        for (status, count) in status_counts {
+            *totals.entry(*status).or_insert(0) += count;
+        }
+    }
+    let row = |label: Vec<String>, status_counts: &BTreeMap<VerificationStatus, u32>| {
+        let counts = STATUSES.map(|status| status_counts.get(&status).copied().unwrap_or(0));
+        label
+            .into_iter()
+            .chain(counts.iter().map(|count| count.to_string()))
+            .chain(std::iter::once(counts.iter().sum::<u32>().to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    Ok(MarkdownTable::new(
+        Some(
+            ["Crate", "Functions with Automatic Harnesses"]
+                .into_iter()
+                .map(String::from)
+                .chain(STATUSES.iter().map(|status| status.to_string()))
+                .chain(std::iter::once("Total".to_string()))
+                .collect::<Vec<_>>(),
+        ),
+        counts
+            .iter()
+            .map(|((fn_crate, class), status_counts)| {
+                row(vec![fn_crate.clone(), class.to_string()], status_counts)
+            })
+            .chain(std::iter::once(row(
+                vec!["Total".to_string(), "".to_string()],
+                &totals,
+            )))
+            .collect(),
+    )?)
+}
+
+/// List the automatic harnesses that took the longest to verify.
+pub fn slowest_harnesses_table(
+    autoharness_md: &AutoHarnessMetadata,
+    verification_results: &HashMap<String, HarnessResult>,
+) -> Result<MarkdownTable> {
+    let mut timed_results = autoharness_md
+        .chosen
+        .iter()
+        .filter_map(|fn_name| verification_results.get(fn_name))
+        .filter_map(|result| result.seconds().map(|seconds| (result, seconds)))
+        .collect::<Vec<_>>();
+    timed_results.sort_by(|(_, seconds_a), (_, seconds_b)| seconds_b.total_cmp(seconds_a));
+
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Slowest Automatic Harnesses".to_string(),
+            "Crate".to_string(),
+            "Result".to_string(),
+            "Verification Time (s)".to_string(),
+        ]),
+        timed_results
+            .into_iter()
+            .take(SLOWEST_HARNESSES_COUNT)
+            .map(|(result, seconds)| {
+                vec![
+                    result.harness.clone(),
+                    result.crate_name().unwrap_or("").to_string(),
+                    result.status().to_string(),
+                    format!("{seconds:.2}"),
+                ]
+            })
+            .collect(),
+    )?)
+}
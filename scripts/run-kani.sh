@@ -336,6 +336,9 @@ main() {
         ./kani_std_analysis.py --crate std \
           --kani-list-file $current_dir/kani-list.json \
           --metrics-file metrics-data-std.json
+        echo "Computing challenge coverage..."
+        cargo run --manifest-path ../autoharness_analyzer/Cargo.toml -- challenges \
+          ../../doc/src/challenges $current_dir/kani-list.json --format json
         popd
     elif [[ "$run_command" == "autoharness-analyzer" ]]; then
         echo "Running Kani autoharness codegen command..."